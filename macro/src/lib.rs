@@ -1,10 +1,10 @@
 //! The derive macro for the Mmio crate.
 
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote, ToTokens, TokenStreamExt};
+use quote::{format_ident, quote, quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
-    parse_macro_input, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput, Field, Fields,
-    Ident, Meta, Path, Token, TypeArray, TypePath,
+    ext::IdentExt, parse_macro_input, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput,
+    Expr, Field, Fields, Ident, LitStr, Meta, Path, Token, TypeArray, TypePath,
 };
 
 #[proc_macro_derive(Mmio, attributes(mmio))]
@@ -16,11 +16,30 @@ pub fn derive_mmio(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .into()
 }
 
+/// Insert reserved fields in front of every field with an `#[mmio(offset = ...)]` attribute, so
+/// that the field ends up at the given offset.
+#[proc_macro_attribute]
+pub fn mmio_block(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let args = parse_macro_input!(args with Punctuated::<Meta, Token![,]>::parse_terminated);
+    let input = parse_macro_input!(input as DeriveInput);
+    try_mmio_block(args, input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 fn try_derive_mmio(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let mut is_repr_c = false;
     let mut omit_ctor = false;
     let mut const_ptr = false;
     let mut const_inner = false;
+    let mut bus_width = None;
+    let mut big_endian = false;
+    let mut instances = Vec::new();
+    let mut singletons = false;
+    let mut address_ranges = Vec::new();
     'attr: for attr in input.attrs.iter() {
         if attr.path().is_ident("mmio") {
             if let Meta::List(list) = &attr.meta {
@@ -37,8 +56,48 @@ fn try_derive_mmio(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream>
                         const_inner = true;
                         return Ok(());
                     }
+                    if meta.path.is_ident("bus_width") {
+                        let width: syn::LitInt = meta.value()?.parse()?;
+                        let width = width.base10_parse::<usize>()?;
+                        if ![8, 16, 32, 64].contains(&width) {
+                            return Err(meta.error("`bus_width` must be 8, 16, 32 or 64"));
+                        }
+                        bus_width = Some(width);
+                        return Ok(());
+                    }
+                    if meta.path.is_ident("endian") {
+                        let endian: LitStr = meta.value()?.parse()?;
+                        big_endian = parse_endian(&endian)?;
+                        return Ok(());
+                    }
+                    if meta.path.is_ident("singletons") {
+                        singletons = true;
+                        return Ok(());
+                    }
+                    if meta.path.is_ident("address_ranges") {
+                        let content;
+                        syn::parenthesized!(content in meta.input);
+                        while !content.is_empty() {
+                            address_ranges.push(parse_address_range(&content)?);
+                            if !content.is_empty() {
+                                content.parse::<Token![,]>()?;
+                            }
+                        }
+                        return Ok(());
+                    }
+                    if meta.path.is_ident("instances") {
+                        meta.parse_nested_meta(|instance| {
+                            let Some(name) = instance.path.get_ident().cloned() else {
+                                return Err(instance.error("expected an instance name"));
+                            };
+                            let address: Expr = instance.value()?.parse()?;
+                            instances.push((name, address));
+                            Ok(())
+                        })?;
+                        return Ok(());
+                    }
                     Err(meta.error(
-                        "invalid content of mmio attribute, allowed values: `no_ctors`, `const_ptr`, `const_inner`"
+                        "invalid content of mmio attribute, allowed values: `no_ctors`, `const_ptr`, `const_inner`, `bus_width`, `endian`, `instances`, `singletons`, `address_ranges`"
                     ))
                 }) {
                     return Err(syn::Error::new(input.span(), e));
@@ -80,17 +139,68 @@ fn try_derive_mmio(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream>
         ));
     };
 
+    check_generics(&input.generics)?;
+    let is_generic = !input.generics.params.is_empty();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let block_type = quote! { #ident #ty_generics };
+    // The wrapper type, with the given lifetime and backend in addition to the generic
+    // arguments of the register block
+    let wrapper_type = |lifetime: TokenStream, backend: Option<TokenStream>| {
+        let args = core::iter::once(lifetime)
+            .chain(generic_args(&input.generics))
+            .chain(backend);
+        quote! { #wrapper_ident<#(#args),*> }
+    };
+    // The generics of the register block with a lifetime and a backend parameter added
+    let wrapper_generics = |lifetime: bool, backend: TokenStream| {
+        let mut generics = input.generics.clone();
+        if lifetime {
+            generics.params.insert(0, syn::parse_quote! { 'a });
+        }
+        generics.params.push(syn::parse_quote! { #backend });
+        generics
+    };
+
+    if singletons && instances.is_empty() {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "`#[mmio(singletons)]` requires `#[mmio(instances(...))]`",
+        ));
+    }
+    if is_generic && !instances.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "`#[mmio(instances(...))]` is not supported on generic register blocks",
+        ));
+    }
+    // The layout of a generic register block is checked by its constructors
+    if is_generic && omit_ctor {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "`#[mmio(no_ctors)]` is not supported on generic register blocks",
+        ));
+    }
+
     let config = FieldConfig {
+        base_ptr: quote! { self.ptr },
         const_ptr,
         const_inner,
+        bus_width,
+        big_endian,
+        captures: core::iter::once(quote! { 'a })
+            .chain(generic_args(&input.generics))
+            .collect(),
+    };
+    let mut field_parser = FieldParser::new(config.clone());
+    let register_fields = || {
+        fields
+            .named
+            .iter()
+            .map(|field| (field, field.ident.as_ref().unwrap()))
+            .filter(|(_field, field_ident)| !field_ident.to_string().starts_with("_"))
     };
-    let mut field_parser = FieldParser::new(config);
     // process the input to create the fragments we want
-    let access_methods = fields
-        .named
-        .iter()
-        .map(|field| (field, field.ident.as_ref().unwrap()))
-        .filter(|(_field, field_ident)| !field_ident.to_string().starts_with("_"))
+    let access_methods = register_fields()
         .map(|(field, field_ident)| {
             field_parser.generate_access_methods(&ident, field, field_ident)
         })
@@ -99,8 +209,54 @@ fn try_derive_mmio(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream>
     let access_methods_quoted = quote! {
         #(#access_methods)*
     };
-    let field_sizes = fields.named.iter().map(field_size);
+    let field_sizes = fields.named.iter().map(field_size).collect::<Vec<_>>();
+    let offset_checks = fields
+        .named
+        .iter()
+        .map(|field| field_offset_check(ident, field, is_generic))
+        .collect::<syn::Result<Vec<_>>>()?;
     let bound_checks = &field_parser.bound_checks;
+    let register_infos = &field_parser.register_infos;
+    let width_checks = &field_parser.width_checks;
+    // The layout of a generic register block depends on its generic arguments, so
+    // it is checked when a handle is created or the register table is used instead
+    let (layout_checks, layout_check, generic_layout_check) = if is_generic {
+        (
+            None,
+            Some(quote! {
+                #[doc(hidden)]
+                const __LAYOUT_CHECK: () = {
+                    assert!(
+                        0 #( + #field_sizes )* == core::mem::size_of::<Self>(),
+                        "`#[derive(Mmio)]` structures must not contain padding"
+                    );
+                    #(#offset_checks)*
+                    #(#width_checks;)*
+                };
+            }),
+            Some(quote! {
+                let () = Self::__LAYOUT_CHECK;
+            }),
+        )
+    } else {
+        (
+            Some(quote! {
+                impl #wrapper_ident<'_> {
+                    const _FIELD_SIZE: usize = {
+                        0 #( + #field_sizes )*
+                    };
+
+                    // Must match expected size
+                    const _SIZE_CHECK: [(); #wrapper_ident::_FIELD_SIZE] = [(); core::mem::size_of::<#ident>()];
+                }
+
+                #(#offset_checks)*
+                #(const _: () = #width_checks;)*
+            }),
+            None,
+            None,
+        )
+    };
     let mut bound_check_func = TokenStream::new();
     if !bound_checks.is_empty() {
         bound_check_func.append_all(quote! {
@@ -111,6 +267,8 @@ fn try_derive_mmio(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream>
         });
     }
 
+    let wrapper_static = wrapper_type(quote! { 'static }, None);
+    let wrapper_static_with_backend = wrapper_type(quote! { 'static }, Some(quote! { B }));
     let constructors = if omit_ctor {
         None
     } else {
@@ -120,8 +278,34 @@ fn try_derive_mmio(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream>
             /// # Safety
             ///
             /// See the safety notes for [Self::new_mmio].
-            pub const unsafe fn new_mmio_at(addr: usize) -> #wrapper_ident<'static> {
-                Self::new_mmio(addr as *mut #ident)
+            pub const unsafe fn new_mmio_at(addr: usize) -> #wrapper_static {
+                Self::new_mmio(addr as *mut Self)
+            }
+
+            /// Create a new handle to this peripheral given an address, after checking
+            /// that the address is non-null, suitably aligned, that the block does not
+            /// extend past the end of the address space and, if the register block
+            /// declares `#[mmio(address_ranges(...))]`, that the whole block lies within
+            /// one of the ranges.
+            ///
+            /// # Safety
+            ///
+            /// The checks only cover the value of the address. The caller must still
+            /// ensure that:
+            ///
+            /// - the address points to an instance of this peripheral, which is mapped
+            ///   for the whole size of the register block and matches its layout,
+            /// - no other handle to the same peripheral is used concurrently in a way that
+            ///   leads to read-modify-write races, and
+            /// - the handle may be sent to other threads, as described for [Self::new_mmio].
+            #[inline]
+            pub const unsafe fn try_new_mmio_at(
+                addr: usize,
+            ) -> Result<#wrapper_static, derive_mmio::InvalidAddress> {
+                match derive_mmio::InvalidAddress::check::<Self>(addr, &[#(#address_ranges),*]) {
+                    Ok(()) => Ok(Self::new_mmio_at(addr)),
+                    Err(e) => Err(e),
+                }
             }
 
             /// Create a new handle to this peripheral.
@@ -142,7 +326,26 @@ fn try_derive_mmio(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream>
             /// In that case, it it is recommended to [un-implement Send](https://doc.rust-lang.org/nomicon/send-and-sync.html).
             /// on the register block structure.
             #[inline]
-            pub const unsafe fn new_mmio(ptr: *mut #ident) -> #wrapper_ident<'static> {
+            pub const unsafe fn new_mmio(ptr: *mut Self) -> #wrapper_static {
+                #generic_layout_check
+                #wrapper_ident {
+                    ptr,
+                    phantom: core::marker::PhantomData,
+                }
+            }
+
+            /// Create a new handle to this peripheral which performs all register accesses
+            /// through the given [derive_mmio::Backend].
+            ///
+            /// # Safety
+            ///
+            /// See the safety notes for [Self::new_mmio]. The pointer must also be valid for
+            /// the chosen backend.
+            #[inline]
+            pub const unsafe fn new_mmio_with_backend<B: derive_mmio::Backend>(
+                ptr: *mut Self,
+            ) -> #wrapper_static_with_backend {
+                #generic_layout_check
                 #wrapper_ident {
                     ptr,
                     phantom: core::marker::PhantomData,
@@ -151,7 +354,41 @@ fn try_derive_mmio(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream>
         })
     };
 
-    let vis = input.vis;
+    let vis = &input.vis;
+    let fixed_handle = if instances.is_empty() {
+        None
+    } else {
+        // The same methods, with the address given by the handle type. The checks
+        // were already generated above.
+        let mut fixed_parser = FieldParser::new(FieldConfig {
+            base_ptr: quote! { (BASE as *mut #ident) },
+            captures: vec![quote! { BASE }],
+            ..config
+        });
+        let fixed_access_methods = register_fields()
+            .map(|(field, field_ident)| {
+                fixed_parser.generate_access_methods(ident, field, field_ident)
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        Some(fixed_handle(
+            ident,
+            vis,
+            &wrapper_ident,
+            &instances,
+            &address_ranges,
+            omit_ctor,
+            singletons,
+            fixed_access_methods,
+        ))
+    };
+    let wrapper_decl_generics = wrapper_generics(true, quote! { B = derive_mmio::VolatileBackend });
+    let wrapper_impl_generics = wrapper_generics(true, quote! { B: derive_mmio::Backend });
+    let (wrapper_impl_generics, _, _) = wrapper_impl_generics.split_for_impl();
+    let backend_generics = wrapper_generics(false, quote! { B });
+    let (backend_impl_generics, _, _) = backend_generics.split_for_impl();
+    let wrapper_any = wrapper_type(quote! { '_ }, Some(quote! { B }));
+    let wrapper_a = wrapper_type(quote! { 'a }, Some(quote! { B }));
+    let send_predicates = where_clause.map(|where_clause| &where_clause.predicates);
 
     // combine the fragments into the desired output code
     let tokens = quote! {
@@ -159,12 +396,12 @@ fn try_derive_mmio(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream>
         #[doc = stringify!(#ident)]
         #[doc = "]"]
         #[repr(transparent)]
-        pub struct #wrapper_ident<'a> {
-            ptr: *mut #ident,
-            phantom: core::marker::PhantomData<&'a ()>,
+        pub struct #wrapper_ident #wrapper_decl_generics #where_clause {
+            ptr: *mut #block_type,
+            phantom: core::marker::PhantomData<(&'a (), B)>,
         }
 
-        impl core::fmt::Debug for #wrapper_ident<'_> {
+        impl #backend_impl_generics core::fmt::Debug for #wrapper_any #where_clause {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 f.debug_tuple(stringify!(#wrapper_ident))
                     .field(&self.ptr)
@@ -172,14 +409,11 @@ fn try_derive_mmio(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream>
             }
         }
 
-        impl #wrapper_ident<'_> {
-            const _FIELD_SIZE: usize = {
-                0 #( + #field_sizes )*
-            };
+        #layout_checks
 
-            // Must match expected size
-            const _SIZE_CHECK: [(); #wrapper_ident::_FIELD_SIZE] = [(); core::mem::size_of::<#ident>()];
+        #fixed_handle
 
+        impl #wrapper_impl_generics #wrapper_a #where_clause {
             /// Unsafely clone the MMIO handle.
             ///
             /// # Safety
@@ -196,14 +430,24 @@ fn try_derive_mmio(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream>
 
             /// Retrieve the base pointer for this MMIO handle.
             #[inline]
-            #vis const unsafe fn ptr(&self) -> *mut #ident {
+            #vis const unsafe fn ptr(&self) -> *mut #block_type {
                 self.ptr
             }
 
+            /// Read all registers which can be read without side effects into a
+            /// [derive_mmio::Snapshot], including the registers of inner blocks.
+            ///
+            /// Registers marked with `#[mmio(Read)]` are skipped.
+            #[inline]
+            pub fn snapshot(&self) -> derive_mmio::Snapshot<#block_type> {
+                // Safety: The handle points to a valid register block for the backend
+                unsafe { derive_mmio::Snapshot::read::<B>(self.ptr) }
+            }
+
             #access_methods_quoted
         }
 
-        unsafe impl derive_mmio::_MmioMarker for #wrapper_ident<'_> {}
+        unsafe impl #backend_impl_generics derive_mmio::_MmioMarker for #wrapper_any #where_clause {}
 
         /// The [core::marker::Send] trait is unsafely implemented because sending a register block pointer to another
         /// thread should not be an issue for most use-cases.
@@ -213,18 +457,348 @@ fn try_derive_mmio(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream>
         ///
         /// In that case, it it is recommended to [un-implement Send](https://doc.rust-lang.org/nomicon/send-and-sync.html).
         /// on the register block structure.
-        unsafe impl core::marker::Send for #wrapper_ident<'_> where #ident: core::marker::Send {}
+        unsafe impl #backend_impl_generics core::marker::Send for #wrapper_any
+        where
+            #block_type: core::marker::Send,
+            #send_predicates
+        {}
+
+        impl #impl_generics #block_type #where_clause {
+            /// Description of all registers in this register block, ordered by offset.
+            pub const REGISTERS: &'static [derive_mmio::RegisterInfo] = {
+                #generic_layout_check
+                &[
+                    #(#register_infos,)*
+                ]
+            };
 
-        impl #ident {
             #bound_check_func
 
+            #layout_check
+
             #constructors
         }
 
+        impl #impl_generics derive_mmio::RegisterBlock for #block_type #where_clause {
+            type Mmio<'a, B> = #wrapper_a;
+
+            #[inline]
+            unsafe fn new_mmio_with_backend<'a, B: derive_mmio::Backend>(
+                ptr: *mut Self,
+            ) -> #wrapper_a {
+                #generic_layout_check
+                #wrapper_ident {
+                    ptr,
+                    phantom: core::marker::PhantomData,
+                }
+            }
+
+            const REGISTERS: &'static [derive_mmio::RegisterInfo] = Self::REGISTERS;
+
+            #[inline]
+            fn __mmio_ptr<'a, B>(mmio: &#wrapper_a) -> *mut Self {
+                mmio.ptr
+            }
+        }
+
     };
     Ok(tokens)
 }
 
+fn try_mmio_block(
+    args: Punctuated<Meta, Token![,]>,
+    mut input: DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut size = None;
+    for meta in args {
+        match meta {
+            Meta::NameValue(name_value) if name_value.path.is_ident("size") => {
+                size = Some(name_value.value)
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    meta.span(),
+                    "invalid content of mmio_block attribute, allowed values: `size = ...`",
+                ))
+            }
+        }
+    }
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "`#[mmio_block]` does not support generic structs",
+        ));
+    }
+    let ident = input.ident.clone();
+    let Data::Struct(ref mut s) = input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "`#[mmio_block]` only supports struct",
+        ));
+    };
+    let Fields::Named(ref mut fields) = s.fields else {
+        return Err(syn::Error::new(
+            input.span(),
+            "`#[mmio_block]` only supports structs with named fields",
+        ));
+    };
+
+    // The offset of the end of the previous field, as a constant expression
+    let mut end = quote! { 0usize };
+    let mut gaps = 0usize;
+    let mut padded_fields = Punctuated::<Field, Token![,]>::new();
+    let mut add_gap =
+        |fields: &mut Punctuated<Field, Token![,]>, offset: &Expr, end: &TokenStream| {
+            let gap_ident = format_ident!("__reserved{}", gaps);
+            gaps += 1;
+            fields.push(syn::parse_quote_spanned! {offset.span()=>
+                #gap_ident: [u8; usize::saturating_sub(#offset, #end)]
+            });
+        };
+    for field in core::mem::take(&mut fields.named) {
+        if let Some(offset) = field_offset(&field)? {
+            // Overlapping fields are caught by the offset check of `#[derive(Mmio)]`
+            add_gap(&mut padded_fields, &offset, &end);
+            end = offset.to_token_stream();
+        }
+        let ty = &field.ty;
+        end = quote! { #end + core::mem::size_of::<#ty>() };
+        padded_fields.push(field);
+    }
+    let size_check = size.map(|size| {
+        add_gap(&mut padded_fields, &size, &end);
+        quote_spanned! {size.span()=>
+            // Must match the size given in the attribute
+            const _: [(); #size] = [(); core::mem::size_of::<#ident>()];
+        }
+    });
+    fields.named = padded_fields;
+
+    Ok(quote! {
+        #input
+        #size_check
+    })
+}
+
+/// Parse an address range like `0x4000_0000..0x4001_0000` of `#[mmio(address_ranges(...))]`.
+///
+/// The bounds are collected as tokens, as expressions only parse ranges as a whole with the
+/// `full` feature of syn.
+fn parse_address_range(input: syn::parse::ParseStream) -> syn::Result<TokenStream> {
+    let error = || input.error("expected an address range like `0x4000_0000..0x4001_0000`");
+    let mut start = TokenStream::new();
+    while !input.is_empty() && !input.peek(Token![..]) && !input.peek(Token![,]) {
+        start.append(input.parse::<proc_macro2::TokenTree>()?);
+    }
+    if start.is_empty() || !input.peek(Token![..]) || input.peek(Token![..=]) {
+        return Err(error());
+    }
+    input.parse::<Token![..]>()?;
+    let mut end = TokenStream::new();
+    while !input.is_empty() && !input.peek(Token![,]) {
+        end.append(input.parse::<proc_macro2::TokenTree>()?);
+    }
+    if end.is_empty() {
+        return Err(error());
+    }
+    Ok(quote! { (#start)..(#end) })
+}
+
+/// Generate the zero-sized `Mmio${Struct}At` handle type with the base address as a
+/// const generic parameter, and a type alias for every instance given in
+/// `#[mmio(instances(...))]`
+///
+/// With `#[mmio(singletons)]`, every instance also gets a flag which tracks whether
+/// its handle was taken.
+#[allow(clippy::too_many_arguments)]
+fn fixed_handle(
+    ident: &Ident,
+    vis: &syn::Visibility,
+    wrapper_ident: &Ident,
+    instances: &[(Ident, Expr)],
+    address_ranges: &[TokenStream],
+    omit_ctor: bool,
+    singletons: bool,
+    access_methods: Vec<TokenStream>,
+) -> TokenStream {
+    let fixed_ident = format_ident!("{}At", wrapper_ident);
+    let range_check = (!address_ranges.is_empty()).then(|| {
+        quote! {
+            assert!(
+                derive_mmio::InvalidAddress::check::<#ident>(BASE, &[#(#address_ranges),*])
+                    .is_ok(),
+                "the register block must be within one of the address ranges"
+            );
+        }
+    });
+    let address_check = (!omit_ctor || singletons).then(|| {
+        quote! {
+            #[doc(hidden)]
+            const __ADDRESS_CHECK: () = {
+                assert!(BASE != 0, "the base address must not be null");
+                assert!(
+                    BASE % core::mem::align_of::<#ident>() == 0,
+                    "the base address must be aligned"
+                );
+                assert!(
+                    BASE.checked_add(core::mem::size_of::<#ident>()).is_some(),
+                    "the register block must not extend past the end of the address space"
+                );
+                #range_check
+            };
+        }
+    });
+    let taken_flags_ident = format_ident!("__{}_TAKEN", ident.to_string().to_uppercase());
+    let (taken_flags, singleton_functions) = if singletons {
+        let count = instances.len();
+        let indices = 0..count;
+        let addresses = instances.iter().map(|(_, address)| address);
+        (
+            Some(quote! {
+                #[doc(hidden)]
+                static #taken_flags_ident: [derive_mmio::TakenFlag; #count] =
+                    [const { derive_mmio::TakenFlag::new() }; #count];
+            }),
+            Some(quote! {
+                #[doc(hidden)]
+                const __INSTANCE: usize = #(if BASE == (#addresses) { #indices } else)* {
+                    panic!("the base address is not one of the instances given in `#[mmio(instances(...))]`")
+                };
+
+                /// Take the handle of this instance, unless it was taken before and not
+                /// released since.
+                ///
+                /// Only one handle taken this way exists at a time, which makes this a safe
+                /// alternative to the unsafe constructors. Only the instances given in
+                /// `#[mmio(instances(...))]` can be taken. The instance is given back with
+                /// [derive_mmio::Taken::release].
+                #[inline]
+                pub fn take() -> Option<derive_mmio::Taken<#wrapper_ident<'static, B>>> {
+                    let flag = &#taken_flags_ident[Self::__INSTANCE];
+                    if flag.take() {
+                        let () = Self::__ADDRESS_CHECK;
+                        let handle = #wrapper_ident {
+                            ptr: BASE as *mut #ident,
+                            phantom: core::marker::PhantomData,
+                        };
+                        // Safety: The flag of this instance was set above
+                        Some(unsafe { derive_mmio::Taken::__new_internal(handle, flag) })
+                    } else {
+                        None
+                    }
+                }
+            }),
+        )
+    } else {
+        (None, None)
+    };
+    let constructor = (!omit_ctor).then(|| {
+        quote! {
+            /// Create a new handle to the peripheral at the address `BASE`.
+            ///
+            /// The address is checked to be non-null and suitably aligned, and the block
+            /// to not extend past the end of the address space, at compile time.
+            ///
+            /// # Safety
+            ///
+            #[doc = concat!("See the safety notes for [", stringify!(#ident), "::new_mmio].")]
+            /// The address must also be valid for the chosen backend.
+            #[inline]
+            pub const unsafe fn new() -> Self {
+                let () = Self::__ADDRESS_CHECK;
+                Self {
+                    phantom: core::marker::PhantomData,
+                }
+            }
+        }
+    });
+    let instance_names = instances.iter().map(|(name, _)| name);
+    let instance_addresses = instances.iter().map(|(_, address)| address);
+    quote! {
+        #[doc = "A zero-sized MMIO handle for the ["]
+        #[doc = stringify!(#ident)]
+        #[doc = "] register block at the address `BASE`."]
+        #[doc = ""]
+        #[doc = "The base address is part of the type, so the handle does not need to store it"]
+        #[doc = "and register addresses are known at compile time."]
+        pub struct #fixed_ident<const BASE: usize, B = derive_mmio::VolatileBackend> {
+            phantom: core::marker::PhantomData<(*mut #ident, B)>,
+        }
+
+        impl<const BASE: usize, B> core::fmt::Debug for #fixed_ident<BASE, B> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_tuple(stringify!(#fixed_ident))
+                    .field(&(BASE as *mut #ident))
+                    .finish()
+            }
+        }
+
+        /// The [core::marker::Send] trait is unsafely implemented for the same reasons as for
+        #[doc = concat!("[", stringify!(#wrapper_ident), "].")]
+        unsafe impl<const BASE: usize, B> core::marker::Send for #fixed_ident<BASE, B>
+        where
+            #ident: core::marker::Send
+        {}
+
+        #taken_flags
+
+        impl<const BASE: usize, B: derive_mmio::Backend> #fixed_ident<BASE, B> {
+            #address_check
+
+            #constructor
+
+            #singleton_functions
+
+            /// Unsafely clone the MMIO handle.
+            ///
+            /// # Safety
+            ///
+            /// This allows to create multiple instances of the same MMIO handle. The user must ensure
+            /// that these handles are not used concurrently in a way that leads to data races.
+            #[inline]
+            pub const unsafe fn clone(&self) -> Self {
+                Self {
+                    phantom: core::marker::PhantomData,
+                }
+            }
+
+            /// Retrieve the base pointer for this MMIO handle.
+            #[inline]
+            #vis const unsafe fn ptr(&self) -> *mut #ident {
+                BASE as *mut #ident
+            }
+
+            /// Borrow this handle as a regular
+            #[doc = concat!("[", stringify!(#wrapper_ident), "]")]
+            /// handle, for example to pass it to generic code.
+            #[inline]
+            pub fn as_mmio(&mut self) -> #wrapper_ident<'_, B> {
+                #wrapper_ident {
+                    ptr: BASE as *mut #ident,
+                    phantom: core::marker::PhantomData,
+                }
+            }
+
+            /// Read all registers which can be read without side effects into a
+            /// [derive_mmio::Snapshot], including the registers of inner blocks.
+            ///
+            /// Registers marked with `#[mmio(Read)]` are skipped.
+            #[inline]
+            pub fn snapshot(&self) -> derive_mmio::Snapshot<#ident> {
+                // Safety: The handle points to a valid register block for the backend
+                unsafe { derive_mmio::Snapshot::read::<B>(BASE as *mut #ident) }
+            }
+
+            #(#access_methods)*
+        }
+
+        #(
+            #[doc = concat!("The [", stringify!(#ident), "] instance at address `", stringify!(#instance_addresses), "`.")]
+            #vis type #instance_names = #fixed_ident<{ #instance_addresses }>;
+        )*
+    }
+}
+
 /// Convert a field into code that returns the field size
 fn field_size(field: &Field) -> TokenStream {
     let ty = &field.ty;
@@ -233,13 +807,322 @@ fn field_size(field: &Field) -> TokenStream {
     }
 }
 
-#[derive(Debug, Default, PartialEq, Eq)]
+/// Convert a field with an `#[mmio(offset = ...)]` attribute into a compile time check that
+/// the field is located at the given offset
+///
+/// The check of a generic register block is an assertion for its `__LAYOUT_CHECK`
+/// constant.
+fn field_offset_check(
+    ident: &Ident,
+    field: &Field,
+    generic: bool,
+) -> syn::Result<Option<TokenStream>> {
+    let field_ident = field.ident.as_ref().unwrap();
+    Ok(field_offset(field)?.map(|offset| {
+        if generic {
+            quote_spanned! {field_ident.span()=>
+                assert!(
+                    core::mem::offset_of!(Self, #field_ident) == #offset,
+                    concat!(
+                        "`",
+                        stringify!(#field_ident),
+                        "` is not located at the offset given in the field attribute"
+                    )
+                );
+            }
+        } else {
+            quote_spanned! {field_ident.span()=>
+                // Must match the offset given in the field attribute
+                const _: [(); #offset] = [(); core::mem::offset_of!(#ident, #field_ident)];
+            }
+        }
+    }))
+}
+
+/// Check that the generic parameters of a register block do not clash with the
+/// generated code
+fn check_generics(generics: &syn::Generics) -> syn::Result<()> {
+    for param in generics.params.iter() {
+        let ident = match param {
+            syn::GenericParam::Lifetime(lifetime) => {
+                return Err(syn::Error::new(
+                    lifetime.span(),
+                    "`#[derive(Mmio)]` does not support lifetime parameters",
+                ))
+            }
+            syn::GenericParam::Type(type_param) => &type_param.ident,
+            syn::GenericParam::Const(const_param) => &const_param.ident,
+        };
+        if ident == "B" || ident == "F" || ident == "I" {
+            return Err(syn::Error::new(
+                ident.span(),
+                format!(
+                    "the generic parameter name `{}` is reserved by `#[derive(Mmio)]`",
+                    ident
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The generic arguments matching the generic parameters of a register block
+fn generic_args(generics: &syn::Generics) -> impl Iterator<Item = TokenStream> + '_ {
+    generics.params.iter().map(|param| match param {
+        syn::GenericParam::Lifetime(lifetime) => lifetime.lifetime.to_token_stream(),
+        syn::GenericParam::Type(type_param) => type_param.ident.to_token_stream(),
+        syn::GenericParam::Const(const_param) => const_param.ident.to_token_stream(),
+    })
+}
+
+/// The MMIO handle type of an inner register block, with the given lifetime and
+/// the backend `B`
+///
+/// The handle type is named through the `RegisterBlock` trait, so that an inner
+/// block without `#[derive(Mmio)]` is reported as a missing trait implementation.
+fn inner_mmio_type(inner_type: &TypePath, lifetime: TokenStream) -> TokenStream {
+    quote! { <#inner_type as derive_mmio::RegisterBlock>::Mmio<#lifetime, B> }
+}
+
+/// Get the value of the `#[mmio(offset = ...)]` attribute of a field, if there is one
+fn field_offset(field: &Field) -> syn::Result<Option<Expr>> {
+    let mut offset = None;
+    for attr in field.attrs.iter() {
+        if !attr.path().is_ident("mmio") {
+            continue;
+        }
+        let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        for meta in nested {
+            if !meta.path().is_ident("offset") {
+                continue;
+            }
+            let Meta::NameValue(name_value) = meta else {
+                return Err(syn::Error::new(
+                    meta.span(),
+                    "`#[mmio(offset = ...)]` requires an offset value",
+                ));
+            };
+            if offset.is_some() {
+                return Err(syn::Error::new(
+                    name_value.span(),
+                    "`#[mmio(...)]` found second offset argument",
+                ));
+            }
+            offset = Some(name_value.value);
+        }
+    }
+    Ok(offset)
+}
+
+/// Convert a field into a `derive_mmio::RegisterInfo` describing it
+fn field_register_info(
+    field_ident: &Ident,
+    ty: &syn::Type,
+    access: TokenStream,
+    inner: bool,
+) -> TokenStream {
+    let (elem, len) = match ty {
+        syn::Type::Array(type_array) => {
+            let len = &type_array.len;
+            (type_array.elem.as_ref(), quote! { Some(#len) })
+        }
+        _ => (ty, quote! { None }),
+    };
+    let inner = if inner {
+        // The name of the type without its module path and generic arguments
+        let name = match elem {
+            syn::Type::Path(type_path) => match type_path.path.segments.last() {
+                Some(segment) => segment.ident.to_string(),
+                None => elem.to_token_stream().to_string(),
+            },
+            _ => elem.to_token_stream().to_string().replace(' ', ""),
+        };
+        quote! {
+            Some(derive_mmio::InnerBlockInfo {
+                name: #name,
+                registers: <#elem as derive_mmio::RegisterBlock>::REGISTERS,
+            })
+        }
+    } else {
+        quote! { None }
+    };
+    let name = field_ident.unraw().to_string();
+    quote! {
+        derive_mmio::RegisterInfo {
+            name: #name,
+            offset: core::mem::offset_of!(Self, #field_ident),
+            size: core::mem::size_of::<#elem>(),
+            len: #len,
+            access: #access,
+            inner: #inner,
+        }
+    }
+}
+
+/// Parse the value of `endian = "big"` or `endian = "little"`, and return whether
+/// it is big endian.
+fn parse_endian(endian: &LitStr) -> syn::Result<bool> {
+    match endian.value().as_str() {
+        "big" => Ok(true),
+        "little" => Ok(false),
+        _ => Err(syn::Error::new(
+            endian.span(),
+            "`endian` must be \"big\" or \"little\"",
+        )),
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 enum ReadAccess {
     // Pure reads, no side effects.
     Pure,
     // Normal read access with side effects.
     #[default]
     Normal,
+    // Reading clears the register.
+    ClearOnRead,
+    // Every read pops a value from a FIFO.
+    Fifo,
+}
+
+/// Options of a `u64` field which is accessed as two 32-bit halves.
+#[derive(Debug, Default, Clone, Copy)]
+struct Split64 {
+    // The high half is accessed first.
+    hi_first: bool,
+    // The hardware latches the second half when the first half is read.
+    latch: bool,
+}
+
+impl Split64 {
+    /// Parse `split64` or `split64(order = "...", latch)`
+    fn parse(meta: &Meta) -> syn::Result<Self> {
+        let mut split = Self::default();
+        match meta {
+            Meta::Path(_) => (),
+            Meta::List(list) => list.parse_nested_meta(|meta| {
+                if meta.path.is_ident("order") {
+                    let order: LitStr = meta.value()?.parse()?;
+                    split.hi_first = match order.value().as_str() {
+                        "lo_hi" => false,
+                        "hi_lo" => true,
+                        _ => {
+                            return Err(syn::Error::new(
+                                order.span(),
+                                "`order` must be \"lo_hi\" or \"hi_lo\"",
+                            ))
+                        }
+                    };
+                } else if meta.path.is_ident("latch") {
+                    split.latch = true;
+                } else {
+                    return Err(meta.error("`split64` only supports `order` and `latch` options"));
+                }
+                Ok(())
+            })?,
+            Meta::NameValue(_) => {
+                return Err(syn::Error::new(
+                    meta.span(),
+                    "expected `split64` or `split64(...)`",
+                ))
+            }
+        }
+        Ok(split)
+    }
+
+    /// The addresses of the two halves, `lo_addr` and `hi_addr`, given the address
+    /// `addr` of the field. The high half of big-endian registers is at the lower
+    /// address.
+    fn addresses(big_endian: bool) -> TokenStream {
+        if big_endian {
+            quote! { let (hi_addr, lo_addr) = (addr, unsafe { addr.add(1) }); }
+        } else {
+            quote! { let (lo_addr, hi_addr) = (addr, unsafe { addr.add(1) }); }
+        }
+    }
+
+    /// The body of a function reading the field `field_ident` as two halves.
+    fn read_body(
+        &self,
+        base_ptr: &TokenStream,
+        field_ident: &Ident,
+        access: &AccessModifiers,
+    ) -> TokenStream {
+        let backend = access.backend();
+        let addresses = Self::addresses(access.big_endian);
+        let addresses = quote! {
+            let addr = unsafe { core::ptr::addr_of!((*#base_ptr).#field_ident) }.cast::<u32>();
+            #addresses
+        };
+        if !self.latch {
+            // Re-read the high half until it did not change while reading the low half
+            return quote! {
+                #addresses
+                loop {
+                    let hi = unsafe { #backend::read_u32(hi_addr) };
+                    let lo = unsafe { #backend::read_u32(lo_addr) };
+                    if unsafe { #backend::read_u32(hi_addr) } == hi {
+                        break (u64::from(hi) << 32) | u64::from(lo);
+                    }
+                }
+            };
+        }
+        let mut reads = [
+            quote! { let lo = unsafe { #backend::read_u32(lo_addr) }; },
+            quote! { let hi = unsafe { #backend::read_u32(hi_addr) }; },
+        ];
+        if self.hi_first {
+            reads.reverse();
+        }
+        quote! {
+            #addresses
+            #(#reads)*
+            (u64::from(hi) << 32) | u64::from(lo)
+        }
+    }
+
+    /// The body of a function writing `value` to the field as two halves.
+    fn write_body(&self, pointer_fn_name: &Ident, access: &AccessModifiers) -> TokenStream {
+        let backend = access.backend();
+        let addresses = Self::addresses(access.big_endian);
+        let mut writes = [
+            quote! { #backend::write_u32(lo_addr, value as u32); },
+            quote! { #backend::write_u32(hi_addr, (value >> 32) as u32); },
+        ];
+        if self.hi_first {
+            writes.reverse();
+        }
+        quote! {
+            let addr = self.#pointer_fn_name().cast::<u32>();
+            #addresses
+            unsafe {
+                #(#writes)*
+            }
+        }
+    }
+
+    /// Documentation of the access order.
+    fn doc(&self, read: bool) -> TokenStream {
+        let (first, second) = if self.hi_first {
+            ("high", "low")
+        } else {
+            ("low", "high")
+        };
+        let order = if read && !self.latch {
+            "The register is read as two 32-bit halves, high, low and high again, until \
+            the high half did not change while reading the low half."
+                .to_string()
+        } else {
+            format!(
+                "The register is accessed as two 32-bit halves, the {} half first and the {} half second.",
+                first, second
+            )
+        };
+        quote! {
+            #[doc = ""]
+            #[doc = #order]
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -247,10 +1130,71 @@ struct AccessModifiers {
     read: Option<ReadAccess>,
     write: bool,
     modify: bool,
+    write_one_to_clear: bool,
+    write_one_to_set: bool,
+    split64: Option<Split64>,
+    big_endian: bool,
 }
 
-impl AccessModifiers {
+impl AccessModifiers {
+    /// Convert the access modifiers into a `derive_mmio::RegisterAccess` value
+    fn register_access(&self) -> TokenStream {
+        let mut flags = Vec::new();
+        match self.read {
+            Some(ReadAccess::Pure) => flags.push(quote! { PURE_READ }),
+            Some(ReadAccess::Normal) => flags.push(quote! { READ }),
+            Some(ReadAccess::ClearOnRead) => flags.push(quote! { CLEAR_ON_READ }),
+            Some(ReadAccess::Fifo) => flags.push(quote! { FIFO }),
+            None => (),
+        }
+        if self.write {
+            flags.push(quote! { WRITE });
+        }
+        if self.modify {
+            flags.push(quote! { MODIFY });
+        }
+        if self.write_one_to_clear {
+            flags.push(quote! { W1C });
+        }
+        if self.write_one_to_set {
+            flags.push(quote! { W1S });
+        }
+        if let Some(split) = self.split64 {
+            flags.push(quote! { SPLIT64 });
+            if split.hi_first {
+                flags.push(quote! { SPLIT64_HI_FIRST });
+            }
+            if split.latch {
+                flags.push(quote! { SPLIT64_LATCH });
+            }
+        }
+        if self.big_endian {
+            flags.push(quote! { BIG_ENDIAN });
+        }
+        quote! {
+            derive_mmio::RegisterAccess::NONE
+                #(.union(derive_mmio::RegisterAccess::#flags))*
+        }
+    }
+
+    /// The backend used to access the field, which byte-swaps big-endian registers
+    fn backend(&self) -> TokenStream {
+        if self.big_endian {
+            quote! { <derive_mmio::BigEndian<B> as derive_mmio::Backend> }
+        } else {
+            quote! { B }
+        }
+    }
+
     pub fn convert_unmodified(&mut self) -> bool {
+        if self.write_one_to_clear || self.write_one_to_set {
+            // Bits are cleared or set by the generated methods, so only reading remains
+            if self.read.is_none() && !self.write {
+                self.read = Some(ReadAccess::Pure);
+                return true;
+            }
+            return false;
+        }
         if self.read.is_none() && !self.write && !self.modify {
             self.read = Some(ReadAccess::Pure);
             self.write = true;
@@ -261,13 +1205,22 @@ impl AccessModifiers {
     }
 }
 
+#[derive(Clone)]
 struct FieldConfig {
+    // The expression for the pointer to the register block in generated methods
+    pub base_ptr: TokenStream,
     pub const_ptr: bool,
     pub const_inner: bool,
+    pub bus_width: Option<usize>,
+    pub big_endian: bool,
+    // The generic parameters of the handle besides the backend, which iterators must capture
+    pub captures: Vec<TokenStream>,
 }
 
 struct FieldParser {
     bound_checks: Vec<TokenStream>,
+    width_checks: Vec<TokenStream>,
+    register_infos: Vec<TokenStream>,
     config: FieldConfig,
 }
 
@@ -275,6 +1228,8 @@ impl FieldParser {
     pub fn new(config: FieldConfig) -> Self {
         Self {
             bound_checks: Vec::new(),
+            width_checks: Vec::new(),
+            register_infos: Vec::new(),
             config,
         }
     }
@@ -285,7 +1240,11 @@ impl FieldParser {
         field: &Field,
         field_ident: &Ident,
     ) -> syn::Result<TokenStream> {
-        let mut access = AccessModifiers::default();
+        let mut access = AccessModifiers {
+            big_endian: self.config.big_endian,
+            ..Default::default()
+        };
+        let mut has_endian = false;
         for attr in field.attrs.iter() {
             if attr.path().is_ident("mmio") {
                 let Ok(nested) =
@@ -297,10 +1256,57 @@ impl FieldParser {
                     ));
                 };
                 let unexpected_meta_printout =
-                    "`#[mmio(...)]` only supports 'Inner', 'Read', 'PureRead', 'Write', and 'Modify' options";
+                    "`#[mmio(...)]` only supports 'Inner', 'Read', 'PureRead', 'ClearOnRead', 'Fifo', 'Write', 'Modify', 'W1C', 'W1S', 'split64', 'endian' and 'offset' options";
                 for meta in nested {
+                    // The offset is checked separately by `field_offset_check`
+                    if meta.path().is_ident("offset") {
+                        continue;
+                    }
+                    if meta.path().is_ident("endian") {
+                        if has_endian {
+                            return Err(syn::Error::new(
+                                attr.span(),
+                                "`#[mmio(...)]` found second endian argument",
+                            ));
+                        }
+                        let Meta::NameValue(name_value) = &meta else {
+                            return Err(syn::Error::new(
+                                meta.span(),
+                                "expected `endian = \"big\"` or `endian = \"little\"`",
+                            ));
+                        };
+                        let syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(endian),
+                            ..
+                        }) = &name_value.value
+                        else {
+                            return Err(syn::Error::new(
+                                name_value.value.span(),
+                                "expected `endian = \"big\"` or `endian = \"little\"`",
+                            ));
+                        };
+                        access.big_endian = parse_endian(endian)?;
+                        has_endian = true;
+                        continue;
+                    }
+                    if meta.path().is_ident("split64") {
+                        if access.split64.is_some() {
+                            return Err(syn::Error::new(
+                                attr.span(),
+                                "`#[mmio(...)]` found second split64 argument",
+                            ));
+                        }
+                        access.split64 = Some(Split64::parse(&meta)?);
+                        continue;
+                    }
                     if let Meta::Path(path) = meta {
                         if path.is_ident("Inner") {
+                            self.register_infos.push(field_register_info(
+                                field_ident,
+                                &field.ty,
+                                quote! { derive_mmio::RegisterAccess::NONE },
+                                true,
+                            ));
                             return self.generate_access_method_for_inner_mmio_field(
                                 ident,
                                 field,
@@ -322,6 +1328,18 @@ impl FieldParser {
                                 ));
                             }
                             access.read = Some(ReadAccess::Pure);
+                        } else if path.is_ident("ClearOnRead") || path.is_ident("Fifo") {
+                            if access.read.is_some() {
+                                return Err(syn::Error::new(
+                                    attr.span(),
+                                    "`#[mmio(...)]` found second read argument",
+                                ));
+                            }
+                            access.read = Some(if path.is_ident("Fifo") {
+                                ReadAccess::Fifo
+                            } else {
+                                ReadAccess::ClearOnRead
+                            });
                         } else if path.is_ident("Write") {
                             if access.write {
                                 return Err(syn::Error::new(
@@ -338,6 +1356,22 @@ impl FieldParser {
                                 ));
                             }
                             access.modify = true;
+                        } else if path.is_ident("W1C") {
+                            if access.write_one_to_clear {
+                                return Err(syn::Error::new(
+                                    attr.span(),
+                                    "`#[mmio(...)]` found second W1C argument",
+                                ));
+                            }
+                            access.write_one_to_clear = true;
+                        } else if path.is_ident("W1S") {
+                            if access.write_one_to_set {
+                                return Err(syn::Error::new(
+                                    attr.span(),
+                                    "`#[mmio(...)]` found second W1S argument",
+                                ));
+                            }
+                            access.write_one_to_set = true;
                         } else {
                             return Err(syn::Error::new(attr.span(), unexpected_meta_printout));
                         }
@@ -354,7 +1388,55 @@ impl FieldParser {
                 "Detected Modify field attribute without read and/or write access specifiers",
             ));
         }
+        if access.modify && (access.write_one_to_clear || access.write_one_to_set) {
+            return Err(syn::Error::new(
+                field.span(),
+                "Modify is not allowed on W1C and W1S fields, because writing back the value read \
+                would also clear or set every other bit which reads as one. \
+                Use the generated `clear_*` and `set_*` methods instead",
+            ));
+        }
+        if access.modify
+            && matches!(
+                access.read,
+                Some(ReadAccess::ClearOnRead) | Some(ReadAccess::Fifo)
+            )
+        {
+            return Err(syn::Error::new(
+                field.span(),
+                "Modify is not allowed on ClearOnRead and Fifo fields, because the value read \
+                is consumed by the read",
+            ));
+        }
+        if access.split64.is_some() {
+            let is_u64 =
+                matches!(&field.ty, syn::Type::Path(type_path) if type_path.path.is_ident("u64"));
+            if !is_u64 {
+                return Err(syn::Error::new(
+                    field.ty.span(),
+                    "`split64` is only supported on `u64` fields",
+                ));
+            }
+            if matches!(
+                access.read,
+                Some(ReadAccess::ClearOnRead) | Some(ReadAccess::Fifo)
+            ) || access.write_one_to_clear
+                || access.write_one_to_set
+            {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "`split64` can not be combined with ClearOnRead, Fifo, W1C or W1S",
+                ));
+            }
+        }
         access.convert_unmodified();
+        self.check_bus_width(field, field_ident, access.split64.is_some())?;
+        self.register_infos.push(field_register_info(
+            field_ident,
+            &field.ty,
+            access.register_access(),
+            false,
+        ));
 
         let mut output = TokenStream::new();
         match &field.ty {
@@ -382,6 +1464,76 @@ impl FieldParser {
         Ok(output)
     }
 
+    /// Check that a register can be accessed atomically on the bus given by
+    /// `#[mmio(bus_width = ...)]`.
+    ///
+    /// Integer types are checked right away, other types with a compile time check.
+    /// Fields marked with `split64` are accessed in 32-bit halves.
+    fn check_bus_width(
+        &mut self,
+        field: &Field,
+        field_ident: &Ident,
+        split64: bool,
+    ) -> syn::Result<()> {
+        let Some(bus_width) = self.config.bus_width else {
+            return Ok(());
+        };
+        if split64 {
+            if bus_width < 32 {
+                return Err(syn::Error::new(
+                    field.ty.span(),
+                    format!(
+                        "`{}` is accessed in 32-bit halves, which can not be accessed atomically \
+                        on the {}-bit bus given by `#[mmio(bus_width = {})]`",
+                        field_ident, bus_width, bus_width
+                    ),
+                ));
+            }
+            return Ok(());
+        }
+        let ty = match &field.ty {
+            syn::Type::Array(type_array) => type_array.elem.as_ref(),
+            ty => ty,
+        };
+        let integer_width = match ty {
+            syn::Type::Path(type_path) => type_path.path.get_ident().and_then(|ident| match ident
+                .to_string()
+                .as_str()
+            {
+                "u8" | "i8" => Some(8),
+                "u16" | "i16" => Some(16),
+                "u32" | "i32" => Some(32),
+                "u64" | "i64" => Some(64),
+                "u128" | "i128" => Some(128),
+                _ => None,
+            }),
+            _ => None,
+        };
+        match integer_width {
+            Some(width) if width > bus_width => Err(syn::Error::new(
+                ty.span(),
+                format!(
+                    "`{}` is {} bits wide and can not be accessed atomically on the {}-bit bus \
+                    given by `#[mmio(bus_width = {})]`",
+                    field_ident, width, bus_width, bus_width
+                ),
+            )),
+            Some(_) => Ok(()),
+            None => {
+                let message = format!(
+                    "`{}` can not be accessed atomically on the {}-bit bus given by \
+                    `#[mmio(bus_width = {})]`",
+                    field_ident, bus_width, bus_width
+                );
+                let bus_bytes = bus_width / 8;
+                self.width_checks.push(quote_spanned! {ty.span()=>
+                    assert!(core::mem::size_of::<#ty>() <= #bus_bytes, #message)
+                });
+                Ok(())
+            }
+        }
+    }
+
     /// Generate access methods for fields that are MMIO blocks.
     pub fn generate_access_method_for_inner_mmio_field(
         &mut self,
@@ -432,6 +1584,7 @@ impl FieldParser {
         array_type: &TypeArray,
         element_type: &TypePath,
     ) -> TokenStream {
+        let base_ptr = &self.config.base_ptr;
         let mut const_token = TokenStream::new();
         if self.config.const_inner {
             const_token.extend(quote! { const });
@@ -457,7 +1610,13 @@ impl FieldParser {
         self.bound_checks.push(quote! {
             derive_mmio::is_mmio::<#inner_mmio_path>();
         });
+        let inner_mmio = inner_mmio_type(element_type, quote! { '_ });
+        let inner_mmio_static = inner_mmio_type(element_type, quote! { 'static });
         let array_len_func = format_ident!("len_{}", field_ident);
+        let iter_func_name = format_ident!("iter_{}", field_ident);
+        let captures = &self.config.captures;
+        let field_ident_at = format_ident!("{}_at", field_ident);
+        let field_ident_shared_at = format_ident!("{}_shared_at", field_ident);
         let field_ident_unchecked = format_ident!("{}_unchecked", field_ident);
 
         let field_ident_shared = format_ident!("{}_shared", field_ident);
@@ -480,7 +1639,7 @@ impl FieldParser {
             #[doc = "The lifetime of the returned inner MMIO block is tied to the"]
             #[doc = "lifetime of this structure"]
             #[inline]
-            pub fn #field_ident(&mut self, index: usize) -> Result<#inner_mmio_path<'_>, #error_type> {
+            pub fn #field_ident(&mut self, index: usize) -> Result<#inner_mmio, #error_type> {
                 if index >= self.#array_len_func() {
                     return Err(#error_type(index));
                 }
@@ -502,7 +1661,7 @@ impl FieldParser {
             #[doc = "based on a raw pointer which might lead to undefined behaviour on invalid offsets."]
             #[doc = "Users MUST ensure that the offset is valid."]
             #[inline]
-            pub #const_token unsafe fn #field_ident_unchecked(&mut self, index: usize) -> #inner_mmio_path<'_> {
+            pub #const_token unsafe fn #field_ident_unchecked(&mut self, index: usize) -> #inner_mmio {
                 unsafe {
                     self.#steal_func_name_unchecked(index)
                 }
@@ -523,7 +1682,7 @@ impl FieldParser {
             #[doc = "you are responsible for ensuring that there are no read-modify-write"]
             #[doc = "races on any of the registers."]
             #[inline]
-            pub fn #field_ident_shared(&self, index: usize) -> Result<derive_mmio::SharedInner<#inner_mmio_path<'_>>, #error_type> {
+            pub fn #field_ident_shared(&self, index: usize) -> Result<derive_mmio::SharedInner<#inner_mmio>, #error_type> {
                 if index >= self.#array_len_func() {
                     return Err(#error_type(index));
                 }
@@ -550,7 +1709,7 @@ impl FieldParser {
             #[doc = "based on a raw pointer which might lead to undefined behaviour on invalid offsets."]
             #[doc = "Users MUST ensure that the offset is valid."]
             #[inline]
-            pub #const_token unsafe fn #field_ident_shared_unchecked(&self, index: usize) -> derive_mmio::SharedInner<#inner_mmio_path<'_>> {
+            pub #const_token unsafe fn #field_ident_shared_unchecked(&self, index: usize) -> derive_mmio::SharedInner<#inner_mmio> {
                 derive_mmio::SharedInner::__new_internal(
                     unsafe {
                         self.#private_steal_unchecked_func_name(index)
@@ -558,6 +1717,55 @@ impl FieldParser {
                 )
             }
 
+            #[doc = "Obtain a reference to the inner MMIO field "]
+            #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+            #[doc = ""]
+            #[doc = "The index is checked at compile time."]
+            #[doc = ""]
+            #[doc = "# Lifetime"]
+            #[doc = ""]
+            #[doc = "The lifetime of the returned inner MMIO block is tied to the"]
+            #[doc = "lifetime of this structure"]
+            #[inline]
+            pub fn #field_ident_at<const I: usize>(&mut self) -> #inner_mmio {
+                const {
+                    assert!(I < #array_len, "index out of bounds");
+                }
+                // Safety: Index was checked.
+                unsafe { self.#field_ident_unchecked(I) }
+            }
+
+            #[doc = "Obtain a shared instance of the inner MMIO field `"]
+            #[doc = stringify!(#field_ident)]
+            #[doc = "`."]
+            #[doc = "This variant only allow access to non-mutable methods of the MMIO block,"]
+            #[doc = "but only requires a shared reference to the outer MMIO block."]
+            #[doc = ""]
+            #[doc = "The index is checked at compile time."]
+            #[inline]
+            pub fn #field_ident_shared_at<const I: usize>(&self) -> derive_mmio::SharedInner<#inner_mmio> {
+                const {
+                    assert!(I < #array_len, "index out of bounds");
+                }
+                // Safety: Index was checked.
+                unsafe { self.#field_ident_shared_unchecked(I) }
+            }
+
+            #[doc = "Iterate over shared instances of all elements of the inner MMIO field `"]
+            #[doc = stringify!(#field_ident)]
+            #[doc = "`."]
+            #[doc = "This only allows access to non-mutable methods of the MMIO blocks,"]
+            #[doc = "see the `_shared` getter."]
+            #[inline]
+            pub fn #iter_func_name(
+                &self,
+            ) -> impl ExactSizeIterator<Item = derive_mmio::SharedInner<#inner_mmio>>
+                   + DoubleEndedIterator
+                   + use<'_, #(#captures,)* B> {
+                // Safety: Every index of the range is in bounds.
+                (0..#array_len).map(move |index| unsafe { self.#field_ident_shared_unchecked(index) })
+            }
+
             #[doc = "Steal inner MMIO field `"]
             #[doc = stringify!(#field_ident)]
             #[doc = "`."]
@@ -570,7 +1778,7 @@ impl FieldParser {
             #[doc = "you are responsible for ensuring that there are no read-modify-write"]
             #[doc = "races on any of the registers."]
             #[inline]
-            pub unsafe fn #steal_func_name(&mut self, index: usize) -> Result<#inner_mmio_path<'static>, #error_type> {
+            pub unsafe fn #steal_func_name(&mut self, index: usize) -> Result<#inner_mmio_static, #error_type> {
                 if index >= self.#array_len_func() {
                     return Err(#error_type(index));
                 }
@@ -593,7 +1801,7 @@ impl FieldParser {
             #[doc = "based on a raw pointer which might lead to undefined behaviour on invalid offsets."]
             #[doc = "Users MUST ensure that the offset is valid."]
             #[inline]
-            pub #const_token unsafe fn #steal_func_name_unchecked(&mut self, index: usize) -> #inner_mmio_path<'static> {
+            pub #const_token unsafe fn #steal_func_name_unchecked(&mut self, index: usize) -> #inner_mmio_static {
                 unsafe { self.#private_steal_unchecked_func_name(index) }
             }
 
@@ -612,7 +1820,7 @@ impl FieldParser {
             #[doc = "you are responsible for ensuring that there are no read-modify-write"]
             #[doc = "races on any of the registers."]
             #[inline]
-            pub unsafe fn #steal_func_name_shared(&self, index: usize) -> Result<derive_mmio::SharedInner<#inner_mmio_path<'static>>, #error_type> {
+            pub unsafe fn #steal_func_name_shared(&self, index: usize) -> Result<derive_mmio::SharedInner<#inner_mmio_static>, #error_type> {
                 if index >= self.#array_len_func() {
                     return Err(#error_type(index));
                 }
@@ -638,7 +1846,7 @@ impl FieldParser {
             #[doc = "based on a raw pointer which might lead to undefined behaviour on invalid offsets."]
             #[doc = "Users MUST ensure that the offset is valid."]
             #[inline]
-            pub #const_token unsafe fn #steal_func_name_shared_unchecked(&self, index: usize) -> derive_mmio::SharedInner<#inner_mmio_path<'static>> {
+            pub #const_token unsafe fn #steal_func_name_shared_unchecked(&self, index: usize) -> derive_mmio::SharedInner<#inner_mmio_static> {
                 derive_mmio::SharedInner::__new_internal(
                     unsafe {
                         self.#private_steal_unchecked_func_name(index)
@@ -655,10 +1863,10 @@ impl FieldParser {
             }
 
             #[doc(hidden)]
-            #const_token unsafe fn #private_steal_unchecked_func_name(&self, index: usize) -> #inner_mmio_path<'static> {
-                let ptr = unsafe {(*self.ptr).#field_ident.as_mut_ptr().add(index) };
+            #const_token unsafe fn #private_steal_unchecked_func_name(&self, index: usize) -> #inner_mmio_static {
+                let ptr = unsafe {(*#base_ptr).#field_ident.as_mut_ptr().add(index) };
                 unsafe {
-                    #element_type::new_mmio(ptr)
+                    <#element_type>::new_mmio_with_backend(ptr)
                 }
             }
 
@@ -671,6 +1879,7 @@ impl FieldParser {
         field_ident: &Ident,
         type_path: &TypePath,
     ) -> TokenStream {
+        let base_ptr = &self.config.base_ptr;
         let mut const_token = TokenStream::new();
         if self.config.const_inner {
             const_token.extend(quote! { const });
@@ -695,6 +1904,8 @@ impl FieldParser {
         self.bound_checks.push(quote! {
             derive_mmio::is_mmio::<#inner_mmio_path>();
         });
+        let inner_mmio = inner_mmio_type(type_path, quote! { '_ });
+        let inner_mmio_static = inner_mmio_type(type_path, quote! { 'static });
         let field_ident_shared = format_ident!("{}_shared", field_ident);
         let steal_func_name = format_ident!("steal_{}", field_ident);
         let steal_func_name_shared = format_ident!("steal_{}_shared", field_ident);
@@ -708,7 +1919,7 @@ impl FieldParser {
             #[doc = "The lifetime of the returned inner MMIO block is tied to the"]
             #[doc = "lifetime of this structure"]
             #[inline]
-            pub #const_token fn #field_ident(&mut self) -> #inner_mmio_path<'_> {
+            pub #const_token fn #field_ident(&mut self) -> #inner_mmio {
                 unsafe {
                     self.#steal_func_name()
                 }
@@ -729,7 +1940,7 @@ impl FieldParser {
             #[doc = "you are responsible for ensuring that there are no read-modify-write"]
             #[doc = "races on any of the registers."]
             #[inline]
-            pub #const_token fn #field_ident_shared(&self) -> derive_mmio::SharedInner<#inner_mmio_path<'_>> {
+            pub #const_token fn #field_ident_shared(&self) -> derive_mmio::SharedInner<#inner_mmio> {
                 derive_mmio::SharedInner::__new_internal(
                     unsafe {
                         self.#steal_func_unchecked_name()
@@ -749,7 +1960,7 @@ impl FieldParser {
             #[doc = "you are responsible for ensuring that there are no read-modify-write"]
             #[doc = "races on any of the registers."]
             #[inline]
-            pub #const_token unsafe fn #steal_func_name(&mut self) -> #inner_mmio_path<'static> {
+            pub #const_token unsafe fn #steal_func_name(&mut self) -> #inner_mmio_static {
                 unsafe { self.#steal_func_unchecked_name() }
             }
 
@@ -768,7 +1979,7 @@ impl FieldParser {
             #[doc = "you are responsible for ensuring that there are no read-modify-write"]
             #[doc = "races on any of the registers."]
             #[inline]
-            pub #const_token unsafe fn #steal_func_name_shared(&self) -> derive_mmio::SharedInner<#inner_mmio_path<'static>> {
+            pub #const_token unsafe fn #steal_func_name_shared(&self) -> derive_mmio::SharedInner<#inner_mmio_static> {
                 derive_mmio::SharedInner::__new_internal(
                     unsafe {
                         self.#steal_func_unchecked_name()
@@ -777,10 +1988,10 @@ impl FieldParser {
             }
 
             #[doc(hidden)]
-            #const_token unsafe fn #steal_func_unchecked_name(&self) -> #inner_mmio_path<'static> {
-                let ptr = unsafe { core::ptr::addr_of_mut!((*self.ptr).#field_ident) };
+            #const_token unsafe fn #steal_func_unchecked_name(&self) -> #inner_mmio_static {
+                let ptr = unsafe { core::ptr::addr_of_mut!((*#base_ptr).#field_ident) };
                 unsafe {
-                    #type_path::new_mmio(ptr)
+                    <#type_path>::new_mmio_with_backend(ptr)
                 }
             }
         }
@@ -794,6 +2005,8 @@ impl FieldParser {
         type_path: &TypePath,
         access_methods: &mut TokenStream,
     ) {
+        let backend = access.backend();
+        let base_ptr = &self.config.base_ptr;
         let mut const_token = TokenStream::new();
         if self.config.const_ptr {
             const_token.extend(quote! { const });
@@ -802,6 +2015,8 @@ impl FieldParser {
         let read_fn_name = format_ident!("read_{}", field_ident);
         let write_fn_name = format_ident!("write_{}", field_ident);
         let modify_fn_name = format_ident!("modify_{}", field_ident);
+        let clear_fn_name = format_ident!("clear_{}", field_ident);
+        let set_fn_name = format_ident!("set_{}", field_ident);
 
         access_methods.append_all(quote! {
             #[doc = "Obtain a pointer to the "]
@@ -815,35 +2030,108 @@ impl FieldParser {
             #[doc = "Never create a reference from this pointer - only use read/write/read_volatile/write_volatile methods on it."]
             #[inline(always)]
             pub #const_token fn #pointer_fn_name(&self) -> *mut #type_path{
-                unsafe { core::ptr::addr_of_mut!((*self.ptr).#field_ident) }
+                unsafe { core::ptr::addr_of_mut!((*#base_ptr).#field_ident) }
             }
         });
-        if let Some(read_access) = access.read {
-            let opt_mut = (read_access == ReadAccess::Normal).then_some(quote! { mut });
+        if access.read == Some(ReadAccess::Fifo) {
+            let pop_fn_name = format_ident!("pop_{}", field_ident);
+            let drain_fn_name = format_ident!("drain_{}", field_ident);
+            access_methods.append_all(quote! {
+                #[doc = "Pop a value from the "]
+                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                #[doc = " FIFO register."]
+                #[inline(always)]
+                pub fn #pop_fn_name(&mut self) -> #type_path {
+                    let addr = unsafe { core::ptr::addr_of!((*#base_ptr).#field_ident) };
+                    unsafe {
+                        #backend::read(addr)
+                    }
+                }
+
+                #[doc = "Pop `buf.len()` values from the "]
+                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                #[doc = " FIFO register into `buf`, reading repeatedly from the same address."]
+                #[doc = ""]
+                #[doc = "Returns the number of values read, which is `buf.len()`."]
+                #[inline]
+                pub fn #drain_fn_name(&mut self, buf: &mut [#type_path]) -> usize {
+                    for value in buf.iter_mut() {
+                        *value = self.#pop_fn_name();
+                    }
+                    buf.len()
+                }
+            });
+        } else if let Some(read_access) = access.read {
+            let opt_mut = (read_access != ReadAccess::Pure).then_some(quote! { mut });
+            let doc_clear = (read_access == ReadAccess::ClearOnRead).then_some(quote! {
+                #[doc = ""]
+                #[doc = "Reading the register clears it."]
+            });
+            let doc_split = access.split64.map(|split| split.doc(true));
+            let body = match access.split64 {
+                Some(split) => split.read_body(base_ptr, field_ident, &access),
+                None => quote! {
+                    let addr = unsafe { core::ptr::addr_of!((*#base_ptr).#field_ident) };
+                    unsafe {
+                        #backend::read(addr)
+                    }
+                },
+            };
 
             access_methods.append_all(quote! {
                 #[doc = "Read the "]
                 #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
                 #[doc = " register."]
+                #doc_clear
+                #doc_split
                 #[inline(always)]
                 pub fn #read_fn_name(&#opt_mut self) -> #type_path {
-                    let addr = unsafe { core::ptr::addr_of!((*self.ptr).#field_ident) };
-                    unsafe {
-                        addr.read_volatile()
-                    }
+                    #body
                 }
             });
         }
         if access.write {
+            let doc_split = access.split64.map(|split| split.doc(false));
+            let body = match access.split64 {
+                Some(split) => split.write_body(&pointer_fn_name, &access),
+                None => quote! {
+                    let addr = self.#pointer_fn_name();
+                    unsafe {
+                        #backend::write(addr, value)
+                    }
+                },
+            };
             access_methods.append_all(quote! {
                 #[doc = "Write the "]
                 #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
                 #[doc = " register."]
+                #doc_split
                 #[inline(always)]
                 pub fn #write_fn_name(&mut self, value: #type_path) {
+                    #body
+                }
+            });
+        }
+        // Burst writes only make sense if the register does not act as memory
+        if access.write
+            && access.split64.is_none()
+            && matches!(access.read, None | Some(ReadAccess::Fifo))
+        {
+            let write_from_slice_fn_name = format_ident!("write_{}_from_slice", field_ident);
+            access_methods.append_all(quote! {
+                #[doc = "Write every value in `data` to the "]
+                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                #[doc = " register, in order."]
+                #[doc = ""]
+                #[doc = "All writes go to the same address, which makes this suitable for pushing"]
+                #[doc = "data into a FIFO."]
+                #[inline]
+                pub fn #write_from_slice_fn_name(&mut self, data: &[#type_path]) {
                     let addr = self.#pointer_fn_name();
-                    unsafe {
-                        addr.write_volatile(value)
+                    for value in data {
+                        unsafe {
+                            #backend::write(addr, *value)
+                        }
                     }
                 }
             });
@@ -861,6 +2149,66 @@ impl FieldParser {
             }
         });
         }
+        if access.modify && cfg!(feature = "critical-section") {
+            let modify_cs_fn_name = format_ident!("modify_{}_cs", field_ident);
+            let read_body = match access.split64 {
+                Some(split) => split.read_body(base_ptr, field_ident, &access),
+                None => quote! {
+                    let addr = unsafe { core::ptr::addr_of!((*#base_ptr).#field_ident) };
+                    unsafe {
+                        #backend::read(addr)
+                    }
+                },
+            };
+            let write_body = match access.split64 {
+                Some(split) => split.write_body(&pointer_fn_name, &access),
+                None => quote! {
+                    let addr = self.#pointer_fn_name();
+                    unsafe {
+                        #backend::write(addr, value)
+                    }
+                },
+            };
+            access_methods.append_all(quote! {
+                #[doc = "Read-Modify-Write the "]
+                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                #[doc = " register within a critical section."]
+                #[doc = ""]
+                #[doc = "Unlike the regular modify function, this only requires a shared reference"]
+                #[doc = "to the MMIO handle, because the read and the write can not be interrupted by"]
+                #[doc = "other code which modifies the register within a critical section."]
+                #[inline]
+                pub fn #modify_cs_fn_name<F>(&self, f: F) where F: FnOnce(#type_path) -> #type_path {
+                    derive_mmio::__critical_section::with(|_| {
+                        let value = { #read_body };
+                        let value = f(value);
+                        #write_body
+                    })
+                }
+            });
+        }
+        for (enabled, fn_name, action) in [
+            (access.write_one_to_clear, &clear_fn_name, "clear"),
+            (access.write_one_to_set, &set_fn_name, "set"),
+        ] {
+            if !enabled {
+                continue;
+            }
+            access_methods.append_all(quote! {
+                #[doc = concat!("Write-one-to-", #action, " the bits given by `mask` in the ")]
+                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                #[doc = " register."]
+                #[doc = ""]
+                #[doc = "Bits which are zero in `mask` are left unchanged by the hardware."]
+                #[inline(always)]
+                pub fn #fn_name(&mut self, mask: #type_path) {
+                    let addr = self.#pointer_fn_name();
+                    unsafe {
+                        #backend::write(addr, mask)
+                    }
+                }
+            });
+        }
     }
 
     fn generate_array_access_methods(
@@ -871,6 +2219,8 @@ impl FieldParser {
         type_array: &TypeArray,
         access_methods: &mut TokenStream,
     ) {
+        let backend = access.backend();
+        let base_ptr = &self.config.base_ptr;
         let mut const_token = TokenStream::new();
         if self.config.const_ptr {
             const_token.extend(quote! { const });
@@ -882,10 +2232,19 @@ impl FieldParser {
         let unchecked_read_fn_name = format_ident!("read_{}_unchecked", field_ident);
         let write_fn_name = format_ident!("write_{}", field_ident);
         let unchecked_write_fn_name = format_ident!("write_{}_unchecked", field_ident);
+        let write_from_fn_name = format_ident!("write_{}_from", field_ident);
+        let write_at_fn_name = format_ident!("write_{}_at", field_ident);
+        let modify_at_fn_name = format_ident!("modify_{}_at", field_ident);
+        let read_into_fn_name = format_ident!("read_{}_into", field_ident);
         let unchecked_modify_fn_name = format_ident!("modify_{}_unchecked", field_ident);
         let modify_fn_name = format_ident!("modify_{}", field_ident);
         let array_len_func = format_ident!("len_{}", field_ident);
+        let clear_fn_name = format_ident!("clear_{}", field_ident);
+        let unchecked_clear_fn_name = format_ident!("clear_{}_unchecked", field_ident);
+        let set_fn_name = format_ident!("set_{}", field_ident);
+        let unchecked_set_fn_name = format_ident!("set_{}_unchecked", field_ident);
         let error_type = quote! { derive_mmio::OutOfBoundsError };
+        let range_error_type = quote! { derive_mmio::OutOfRangeError };
 
         access_methods.append_all(quote! {
             #[doc = "Obtain a pointer to the "]
@@ -900,7 +2259,7 @@ impl FieldParser {
             #[doc = "The `add` method method of the pointer can be used to access entries of the array at higher indices."]
             #[inline(always)]
             pub #const_token fn #pointer_fn_name(&self) -> *mut #array_type{
-                unsafe { (*self.ptr).#field_ident.as_mut_ptr() }
+                unsafe { (*#base_ptr).#field_ident.as_mut_ptr() }
             }
         });
 
@@ -915,14 +2274,39 @@ impl FieldParser {
         });
 
         if let Some(read_access) = access.read {
-            let mut opt_mut = TokenStream::new();
-            if read_access == ReadAccess::Normal {
-                opt_mut.append_all(quote! { mut });
-            }
+            let opt_mut = (read_access != ReadAccess::Pure).then_some(quote! { mut });
+            // FIFO registers are popped instead of read
+            let (read_fn_name, unchecked_read_fn_name, read_doc) = if read_access
+                == ReadAccess::Fifo
+            {
+                (
+                    format_ident!("pop_{}", field_ident),
+                    format_ident!("pop_{}_unchecked", field_ident),
+                    quote! {
+                        #[doc = "Pop a value from the "]
+                        #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                        #[doc = " FIFO register."]
+                    },
+                )
+            } else {
+                let doc_clear = (read_access == ReadAccess::ClearOnRead).then_some(quote! {
+                    #[doc = ""]
+                    #[doc = "Reading the register clears it."]
+                });
+                (
+                    read_fn_name.clone(),
+                    unchecked_read_fn_name.clone(),
+                    quote! {
+                        #[doc = "Read the "]
+                        #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                        #[doc = " register."]
+                        #doc_clear
+                    },
+                )
+            };
+            let read_at_fn_name = format_ident!("{}_at", read_fn_name);
             access_methods.append_all(quote! {
-                #[doc = "Read the "]
-                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
-                #[doc = " register."]
+                #read_doc
                 #[doc = ""]
                 #[doc = "# Safety "]
                 #[doc = ""]
@@ -931,16 +2315,14 @@ impl FieldParser {
                 #[doc = "undefined behaviour. Users MUST ensure that the offset is valid."]
                 #[inline(always)]
                 pub unsafe fn #unchecked_read_fn_name(&#opt_mut self, index: usize) -> #array_type {
-                    let ptr = unsafe { (*self.ptr).#field_ident.as_mut_ptr() };
+                    let ptr = unsafe { (*#base_ptr).#field_ident.as_mut_ptr() };
                     // Safety: We're performing a volatile read from a valid memory location
                     unsafe {
-                        core::ptr::read_volatile(ptr.add(index))
+                        #backend::read(ptr.add(index))
                     }
                 }
 
-                #[doc = "Read the "]
-                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
-                #[doc = " register."]
+                #read_doc
                 #[doc = ""]
                 #[doc = "This function also performs bound checking."]
                 #[inline]
@@ -955,7 +2337,85 @@ impl FieldParser {
                     // Safety: Correct index was verified.
                     Ok(unsafe { self.#unchecked_read_fn_name(index) })
                 }
+
+                #read_doc
+                #[doc = ""]
+                #[doc = "The index is checked at compile time."]
+                #[inline]
+                pub fn #read_at_fn_name<const I: usize>(&#opt_mut self) -> #array_type {
+                    const {
+                        assert!(I < #array_len, "index out of bounds");
+                    }
+                    // Safety: Correct index was verified.
+                    unsafe { self.#unchecked_read_fn_name(I) }
+                }
             });
+
+            if read_access == ReadAccess::Pure {
+                let iter_fn_name = format_ident!("iter_{}", field_ident);
+                let captures = &self.config.captures;
+                access_methods.append_all(quote! {
+                    #[doc = "Iterate over the values of all elements of the "]
+                    #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                    #[doc = " register array."]
+                    #[doc = ""]
+                    #[doc = "Every element is read when the iterator reaches it."]
+                    #[inline]
+                    pub fn #iter_fn_name(
+                        &self,
+                    ) -> impl ExactSizeIterator<Item = #array_type> + DoubleEndedIterator + use<'_, #(#captures,)* B> {
+                        // Safety: Every index of the range is in bounds.
+                        (0..#array_len).map(move |index| unsafe { self.#unchecked_read_fn_name(index) })
+                    }
+                });
+            }
+
+            if read_access != ReadAccess::Fifo {
+                access_methods.append_all(quote! {
+                    #[doc = "Read consecutive elements of the "]
+                    #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                    #[doc = " register array, starting at index `start`, into `buf`."]
+                    #[doc = ""]
+                    #[doc = "The whole range is bounds checked before the first read."]
+                    #[inline]
+                    pub fn #read_into_fn_name(
+                        &#opt_mut self,
+                        start: usize,
+                        buf: &mut [#array_type]
+                    ) -> Result<(), #range_error_type> {
+                        #range_error_type::check(start, buf.len(), #array_len)?;
+                        let ptr = self.#pointer_fn_name();
+                        for (index, value) in buf.iter_mut().enumerate() {
+                            // Safety: Bound check was performed.
+                            *value = unsafe { #backend::read(ptr.add(start + index)) };
+                        }
+                        Ok(())
+                    }
+                });
+            }
+
+            if read_access == ReadAccess::Fifo {
+                let drain_fn_name = format_ident!("drain_{}", field_ident);
+                access_methods.append_all(quote! {
+                    #[doc = "Pop `buf.len()` values from the "]
+                    #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                    #[doc = " FIFO register into `buf`, reading repeatedly from the same address."]
+                    #[doc = ""]
+                    #[doc = "Returns the number of values read, which is `buf.len()`, or 0 if the"]
+                    #[doc = "index is out of bounds."]
+                    #[inline]
+                    pub fn #drain_fn_name(&mut self, index: usize, buf: &mut [#array_type]) -> usize {
+                        if index >= #array_len {
+                            return 0;
+                        }
+                        for value in buf.iter_mut() {
+                            // Safety: Correct index was verified.
+                            *value = unsafe { self.#unchecked_read_fn_name(index) };
+                        }
+                        buf.len()
+                    }
+                });
+            }
         }
 
         if access.write {
@@ -972,7 +2432,7 @@ impl FieldParser {
                 pub unsafe fn #unchecked_write_fn_name(&mut self, index: usize, value: #array_type) {
                     // Safety: We're performing a volatile read from a valid memory location
                     unsafe {
-                        core::ptr::write_volatile(self.#pointer_fn_name().add(index), value)
+                        #backend::write(self.#pointer_fn_name().add(index), value)
                     }
                 }
 
@@ -994,6 +2454,40 @@ impl FieldParser {
                     unsafe { self.#unchecked_write_fn_name(index, value) };
                     Ok(())
                 }
+
+                #[doc = "Write the "]
+                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                #[doc = " register."]
+                #[doc = ""]
+                #[doc = "The index is checked at compile time."]
+                #[inline]
+                pub fn #write_at_fn_name<const I: usize>(&mut self, value: #array_type) {
+                    const {
+                        assert!(I < #array_len, "index out of bounds");
+                    }
+                    // Safety: Bound check was performed.
+                    unsafe { self.#unchecked_write_fn_name(I, value) };
+                }
+
+                #[doc = "Write the values in `data` to consecutive elements of the "]
+                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                #[doc = " register array, starting at index `start`."]
+                #[doc = ""]
+                #[doc = "The whole range is bounds checked before the first write."]
+                #[inline]
+                pub fn #write_from_fn_name(
+                    &mut self,
+                    start: usize,
+                    data: &[#array_type]
+                ) -> Result<(), #range_error_type> {
+                    #range_error_type::check(start, data.len(), #array_len)?;
+                    let ptr = self.#pointer_fn_name();
+                    for (index, value) in data.iter().enumerate() {
+                        // Safety: Bound check was performed.
+                        unsafe { #backend::write(ptr.add(start + index), *value) };
+                    }
+                    Ok(())
+                }
             });
         }
 
@@ -1033,6 +2527,114 @@ impl FieldParser {
                     self.#write_fn_name(index, f(value)).unwrap();
                     Ok(())
                 }
+
+                #[doc = "Read-Modify-Write the "]
+                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                #[doc = " register."]
+                #[doc = ""]
+                #[doc = "The index is checked at compile time."]
+                #[inline]
+                pub fn #modify_at_fn_name<const I: usize>(
+                    &mut self,
+                    f: impl FnOnce(#array_type) -> #array_type,
+                ) {
+                    const {
+                        assert!(I < #array_len, "index out of bounds");
+                    }
+                    // Safety: Bound check was performed.
+                    unsafe { self.#unchecked_modify_fn_name(I, f) };
+                }
+            });
+        }
+        if access.modify && cfg!(feature = "critical-section") {
+            let modify_cs_fn_name = format_ident!("modify_{}_cs", field_ident);
+            access_methods.append_all(quote! {
+                #[doc = "Read-Modify-Write the "]
+                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                #[doc = " register within a critical section."]
+                #[doc = ""]
+                #[doc = "Unlike the regular modify function, this only requires a shared reference"]
+                #[doc = "to the MMIO handle, because the read and the write can not be interrupted by"]
+                #[doc = "other code which modifies the register within a critical section."]
+                #[doc = ""]
+                #[doc = "This function also performs bound checking."]
+                #[inline]
+                pub fn #modify_cs_fn_name(
+                    &self,
+                    index: usize,
+                    f: impl FnOnce(#array_type) -> #array_type,
+                ) -> Result<(), #error_type> {
+                    if index >= #array_len {
+                        return Err(#error_type(index));
+                    }
+                    // Safety: Correct index was verified.
+                    let ptr = unsafe { self.#pointer_fn_name().add(index) };
+                    derive_mmio::__critical_section::with(|_| {
+                        // Safety: We're performing volatile accesses to a valid memory location
+                        unsafe {
+                            let value = #backend::read(ptr);
+                            #backend::write(ptr, f(value))
+                        }
+                    });
+                    Ok(())
+                }
+            });
+        }
+
+        for (enabled, fn_name, unchecked_fn_name, action) in [
+            (
+                access.write_one_to_clear,
+                &clear_fn_name,
+                &unchecked_clear_fn_name,
+                "clear",
+            ),
+            (
+                access.write_one_to_set,
+                &set_fn_name,
+                &unchecked_set_fn_name,
+                "set",
+            ),
+        ] {
+            if !enabled {
+                continue;
+            }
+            access_methods.append_all(quote! {
+                #[doc = concat!("Write-one-to-", #action, " the bits given by `mask` in the ")]
+                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                #[doc = " register."]
+                #[doc = ""]
+                #[doc = "# Safety "]
+                #[doc = ""]
+                #[doc = "This function does not perform bounds checking and performs a volatile "]
+                #[doc = "write on a raw pointer with the given offset which might lead to "]
+                #[doc = "undefined behaviour. Users MUST ensure that the offset is valid."]
+                #[inline(always)]
+                pub unsafe fn #unchecked_fn_name(&mut self, index: usize, mask: #array_type) {
+                    // Safety: We're performing a volatile write to a valid memory location
+                    unsafe {
+                        #backend::write(self.#pointer_fn_name().add(index), mask)
+                    }
+                }
+
+                #[doc = concat!("Write-one-to-", #action, " the bits given by `mask` in the ")]
+                #[doc = concat!("[", stringify!(#ident), "::", stringify!(#field_ident), "]")]
+                #[doc = " register."]
+                #[doc = ""]
+                #[doc = "Bits which are zero in `mask` are left unchanged by the hardware."]
+                #[doc = "This function also performs bound checking."]
+                #[inline]
+                pub fn #fn_name(
+                    &mut self,
+                    index: usize,
+                    mask: #array_type
+                ) -> Result<(), #error_type> {
+                    if index >= #array_len {
+                        return Err(#error_type(index));
+                    }
+                    // Safety: Bound check was performed.
+                    unsafe { self.#unchecked_fn_name(index, mask) };
+                    Ok(())
+                }
             });
         }
     }
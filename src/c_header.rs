@@ -0,0 +1,361 @@
+//! Export of register blocks as C headers.
+//!
+//! A [`Header`] formats a C header from the [`RegisterBlock::REGISTERS`] table
+//! of a register block, with a `typedef volatile struct` like the one a C
+//! driver would use, and static assertions which check the offset of every
+//! field. This allows C and Rust code sharing a peripheral to use one register
+//! block definition.
+//!
+//! ```rust
+//! use derive_mmio::c_header::Header;
+//!
+//! #[derive(derive_mmio::Mmio)]
+//! #[repr(C)]
+//! struct Uart {
+//!     data: u32,
+//!     #[mmio(PureRead)]
+//!     status: u32,
+//! }
+//!
+//! let header = Header::<Uart>::new("uart_t").to_string();
+//! assert!(header.contains(
+//!     "typedef volatile struct uart_t {\n    uint32_t data;\n    const uint32_t status;\n} uart_t;\n"
+//! ));
+//! assert!(header.contains("_Static_assert(offsetof(uart_t, status) == 0x4"));
+//! ```
+//!
+//! Registers which can only be read are `const`, and registers which are not 1,
+//! 2, 4 or 8 bytes in size are byte arrays. Fields named like a C keyword, such
+//! as `int`, get a `_` suffix. Reserved fields are not part of the register
+//! table, so gaps between registers are filled with `_reservedN` byte arrays. Inner blocks get a typedef of their own, named
+//! after the inner block type in snake case with a `_t` suffix. Instantiations
+//! of a generic inner block with different registers are numbered, like
+//! `bank_0_t` and `bank_1_t`.
+
+use core::{fmt, marker::PhantomData};
+
+use crate::{RegisterAccess, RegisterBlock, RegisterInfo};
+
+/// A C header with the typedef of a register block.
+///
+/// The header is written by the [`Display`](core::fmt::Display)
+/// implementation. It has an include guard, the typedefs of the register block
+/// and its inner blocks, and static assertions of the offsets and sizes.
+pub struct Header<'a, T: RegisterBlock> {
+    name: &'a str,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: RegisterBlock> Header<'a, T> {
+    /// Describe the register block `T` as a typedef with the given name, like
+    /// `uart_t`.
+    pub const fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: RegisterBlock> fmt::Display for Header<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let guard = Guard(self.name);
+        writeln!(f, "#ifndef {guard}")?;
+        writeln!(f, "#define {guard}")?;
+        writeln!(f)?;
+        writeln!(f, "#include <stddef.h>")?;
+        writeln!(f, "#include <stdint.h>")?;
+        let registers = T::REGISTERS;
+        write_inner_typedefs(f, registers, registers)?;
+        let block = Block {
+            name: &self.name,
+            root: registers,
+            registers,
+            size: core::mem::size_of::<T>(),
+        };
+        block.write(f)?;
+        writeln!(f)?;
+        writeln!(f, "#endif /* {guard} */")
+    }
+}
+
+/// A register block, with the name of its typedef.
+struct Block<'a> {
+    name: &'a dyn fmt::Display,
+    /// The registers of the outermost block, which the typedef names of the
+    /// inner blocks depend on.
+    root: &'static [RegisterInfo],
+    registers: &'static [RegisterInfo],
+    size: usize,
+}
+
+impl Block<'_> {
+    fn write(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.name;
+        writeln!(f)?;
+        writeln!(f, "typedef volatile struct {name} {{")?;
+        let mut end = 0;
+        let mut gaps = 0;
+        for register in self.registers {
+            if register.offset > end {
+                writeln!(
+                    f,
+                    "    uint8_t _reserved{gaps}[{:#x}];",
+                    register.offset - end
+                )?;
+                gaps += 1;
+            }
+            write!(f, "    ")?;
+            let field = FieldName(register.name);
+            // Registers without a matching integer type are byte arrays
+            let mut bytes = None;
+            match register.inner {
+                Some(_) => write!(f, "{}", typedef_name(self.root, register))?,
+                None => {
+                    let read_only = register.access.is_readable() && !is_writable(register.access);
+                    if read_only {
+                        write!(f, "const ")?;
+                    }
+                    match register.size {
+                        1 | 2 | 4 | 8 => write!(f, "uint{}_t", register.size * 8)?,
+                        size => {
+                            write!(f, "uint8_t")?;
+                            bytes = Some(size);
+                        }
+                    }
+                }
+            }
+            write!(f, " {field}")?;
+            if let Some(len) = register.len {
+                write!(f, "[{len}]")?;
+            }
+            if let Some(size) = bytes {
+                write!(f, "[{size}]")?;
+            }
+            writeln!(f, ";")?;
+            end = register.offset + register.total_size();
+        }
+        if self.size > end {
+            writeln!(f, "    uint8_t _reserved{gaps}[{:#x}];", self.size - end)?;
+        }
+        writeln!(f, "}} {name};")?;
+        writeln!(f)?;
+        for register in self.registers {
+            writeln!(
+                f,
+                "_Static_assert(offsetof({name}, {field}) == {offset:#x}, \"{name}.{field} must be at offset {offset:#x}\");",
+                field = FieldName(register.name),
+                offset = register.offset,
+            )?;
+        }
+        writeln!(
+            f,
+            "_Static_assert(sizeof({name}) == {size:#x}, \"{name} must have a size of {size:#x}\");",
+            size = self.size,
+        )
+    }
+}
+
+/// Write the typedefs of the inner blocks in `registers`, and of their inner
+/// blocks, before they are used. The typedef of each inner block is written
+/// once, at its first use in `root`.
+fn write_inner_typedefs(
+    f: &mut fmt::Formatter<'_>,
+    root: &'static [RegisterInfo],
+    registers: &'static [RegisterInfo],
+) -> fmt::Result {
+    for register in registers {
+        let Some(inner) = register.inner else {
+            continue;
+        };
+        write_inner_typedefs(f, root, inner.registers)?;
+        if is_first_use(root, register) {
+            let block = Block {
+                name: &typedef_name(root, register),
+                root,
+                registers: inner.registers,
+                size: register.size,
+            };
+            block.write(f)?;
+        }
+    }
+    Ok(())
+}
+
+/// Call `f` for every inner block field in `registers` and their inner blocks,
+/// in the order of their first use.
+fn for_each_inner(registers: &'static [RegisterInfo], f: &mut impl FnMut(&'static RegisterInfo)) {
+    for register in registers {
+        if let Some(inner) = register.inner {
+            f(register);
+            for_each_inner(inner.registers, f);
+        }
+    }
+}
+
+/// Find the first field in `registers` or their inner blocks which is an inner
+/// block with the same name, size and registers as `block`.
+///
+/// The instantiations of a generic block share its name, so the name alone does
+/// not identify the typedef.
+fn first_use(
+    registers: &'static [RegisterInfo],
+    block: &RegisterInfo,
+) -> Option<&'static RegisterInfo> {
+    registers.iter().find_map(|register| {
+        let inner = register.inner?;
+        if register.size == block.size && Some(inner) == block.inner {
+            return Some(register);
+        }
+        first_use(inner.registers, block)
+    })
+}
+
+/// Whether `register` is the first use of its inner block in `root`.
+fn is_first_use(root: &'static [RegisterInfo], register: &'static RegisterInfo) -> bool {
+    first_use(root, register).is_some_and(|first| core::ptr::eq(first, register))
+}
+
+/// The typedef name of the inner block of `register`. If `root` uses more than
+/// one block with that name, the blocks are numbered in the order of their
+/// first use.
+fn typedef_name(root: &'static [RegisterInfo], register: &RegisterInfo) -> TypedefName {
+    let name = register.inner.map_or("", |inner| inner.name);
+    let mut count = 0;
+    let mut index = 0;
+    for_each_inner(root, &mut |other| {
+        if other.inner.is_some_and(|inner| inner.name == name) && is_first_use(root, other) {
+            if other.size == register.size && other.inner == register.inner {
+                index = count;
+            }
+            count += 1;
+        }
+    });
+    TypedefName {
+        name,
+        index: (count > 1).then_some(index),
+    }
+}
+
+/// Whether the register can be written, including write-one-to-clear and
+/// write-one-to-set registers.
+fn is_writable(access: RegisterAccess) -> bool {
+    access.is_writable()
+        || access.contains(RegisterAccess::W1C)
+        || access.contains(RegisterAccess::W1S)
+}
+
+/// The name of the typedef of an inner block, like `uart_bank_t` for
+/// `UartBank` or `dma_channel_t` for `DMAChannel`, with the number of the block
+/// if several blocks share the name.
+struct TypedefName {
+    name: &'static str,
+    index: Option<usize>,
+}
+
+impl fmt::Display for TypedefName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.name.as_bytes();
+        for (i, c) in self.name.char_indices() {
+            if c.is_ascii_uppercase() {
+                // A word starts after a lowercase letter or digit, or with the
+                // last uppercase letter of an acronym followed by a lowercase one
+                let previous = i.checked_sub(1).map(|i| bytes[i]);
+                let next = bytes.get(i + 1);
+                let word_start = match previous {
+                    Some(previous) if previous.is_ascii_uppercase() => {
+                        next.is_some_and(u8::is_ascii_lowercase)
+                    }
+                    Some(previous) => previous.is_ascii_alphanumeric(),
+                    None => false,
+                };
+                if word_start {
+                    f.write_str("_")?;
+                }
+                fmt::Write::write_char(f, c.to_ascii_lowercase())?;
+            } else {
+                fmt::Write::write_char(f, c)?;
+            }
+        }
+        if let Some(index) = self.index {
+            write!(f, "_{index}")?;
+        }
+        f.write_str("_t")
+    }
+}
+
+/// The name of a field in a typedef, with a `_` suffix if the name of the
+/// register is a C keyword, like `int_` for `int`.
+struct FieldName(&'static str);
+
+impl fmt::Display for FieldName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)?;
+        if KEYWORDS.contains(&self.0) {
+            f.write_str("_")?;
+        }
+        Ok(())
+    }
+}
+
+/// The C keywords up to C23, which can't be used as field names. Keywords
+/// starting with an underscore are missing, since such fields are reserved.
+const KEYWORDS: [&str; 45] = [
+    "alignas",
+    "alignof",
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "char",
+    "const",
+    "constexpr",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "nullptr",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "struct",
+    "switch",
+    "thread_local",
+    "true",
+    "typedef",
+    "typeof",
+    "typeof_unqual",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+];
+
+/// The include guard for a typedef, like `UART_T_H` for `uart_t`.
+struct Guard<'a>(&'a str);
+
+impl fmt::Display for Guard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            fmt::Write::write_char(f, c.to_ascii_uppercase())?;
+        }
+        f.write_str("_H")
+    }
+}
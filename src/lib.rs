@@ -166,6 +166,36 @@ you could create two and use them to read-modify-write *different* registers -
 probably. It depends on whether the registers affect each other or operate
 in isolation.
 
+For base addresses which are only known at runtime, for example from a device
+tree or a PCI BAR, `try_new_mmio_at` checks that the address is non-null and
+aligned for the register block, and that the block does not extend past the end
+of the address space, before creating the handle, and returns an
+[`InvalidAddress`] error otherwise. The `#[mmio(address_ranges(...))]` outer
+attribute additionally restricts the register block to lie within one of the
+given address ranges. The function is still `unsafe`, because the checks can't
+tell whether the peripheral is actually at that address, or whether other
+handles to it exist:
+
+```rust
+#[derive(derive_mmio::Mmio)]
+#[mmio(address_ranges(0x4000_0000..0x4001_0000))]
+#[repr(C)]
+struct Uart {
+    data: u32,
+    status: u32,
+}
+
+assert_eq!(
+    unsafe { Uart::try_new_mmio_at(0x4000_0002) }.unwrap_err(),
+    derive_mmio::InvalidAddress::Misaligned { addr: 0x4000_0002, align: 4 }
+);
+assert_eq!(
+    unsafe { Uart::try_new_mmio_at(0x5000_0000) }.unwrap_err(),
+    derive_mmio::InvalidAddress::OutOfRange(0x5000_0000)
+);
+assert!(unsafe { Uart::try_new_mmio_at(0x4000_1000) }.is_ok());
+```
+
 The constructors shown above will be generated by default. You might want to
 implement custom constructors, for example if your peripheral is only valid for
 one specific address, or a specific set of addresses. You can disable the
@@ -201,6 +231,16 @@ mmio_uart.modify_control(|mut r| {
 });
 ```
 
+With the `critical-section` feature enabled, there is also a
+`modify_${field}_cs` method, which performs the read-modify-write within a
+[`critical-section`] and only needs a shared reference. This is useful for
+registers like clock or reset enables which are shared with interrupt handlers
+holding their own handle.
+
+```rust,ignore
+mmio_clocks.modify_enable_cs(|r| r | 1 << 3);
+```
+
 If you need a pointer to a register, for example if you want to have a DMA
 engine write to a register on your peripheral, you can use this method:
 
@@ -279,11 +319,29 @@ impl MmioPeripheral {
 The [`SharedInner`] wrapper type implements [`Deref`] so it is transparent to
 the user.
 
+Arrays of inner blocks also get an `iter_${field}()` function, which returns an
+iterator over [`SharedInner`] handles for all elements of the array, and
+`${field}_at::<I>()` and `${field}_shared_at::<I>()` getters which check a
+constant index at compile time.
+
 ### Array Fields
 
 Array Fields get two kinds of function - safe ones that perform a bounds check,
 and unsafe ones which skip the bounds check.
 
+The `read_${field}_into` and `write_${field}_from` functions copy a range of
+consecutive elements from or into a slice, with a single bounds check for the
+whole range. An invalid range is reported as an [`OutOfRangeError`] with the
+start, the length and the length of the array.
+
+If the index is a constant, the `read_${field}_at::<I>()`,
+`write_${field}_at::<I>(value)` and `modify_${field}_at::<I>(f)` functions check
+it at compile time instead, so they neither return a `Result` nor need `unsafe`.
+Using an index which is out of bounds is a compile error.
+
+`PureRead` arrays also get an `iter_${field}()` function, which returns an
+iterator over the values of all elements without any bounds checks or panics.
+
 ```rust,ignore
 // Given
 #[derive(Mmio)]
@@ -305,6 +363,14 @@ impl MmioUart {
         // ...
     }
 
+    pub fn read_bank_at<const I: usize>(&self) -> u32 {
+        // ...
+    }
+
+    pub fn read_bank_into(&self, start: usize, buf: &mut [u32]) -> Result<(), OutOfRangeError> {
+        // ...
+    }
+
     pub fn write_bank(&mut self, index: usize, value: u32) -> Result<(), OutOfBoundsError> {
         // ...
     }
@@ -313,6 +379,14 @@ impl MmioUart {
         // ...
     }
 
+    pub fn write_bank_at<const I: usize>(&mut self, value: u32) {
+        // ...
+    }
+
+    pub fn write_bank_from(&mut self, start: usize, data: &[u32]) -> Result<(), OutOfRangeError> {
+        // ...
+    }
+
     pub fn modify_bank<F: FnOnce(u32) -> u32>(&mut self, index: usize, f: F) -> Result<(), OutOfBoundsError> {
         // ...
     }
@@ -321,12 +395,71 @@ impl MmioUart {
         // ...
     }
 
+    pub fn iter_bank(&self) -> impl ExactSizeIterator<Item = u32> + DoubleEndedIterator + '_ {
+        // ...
+    }
+
     pub const fn len_bank(&self) -> usize {
         4
     }
 }
 ```
 
+## Backends
+
+The MMIO handle has an optional second type parameter which selects the
+[`Backend`] used to perform the register accesses. It defaults to
+[`VolatileBackend`], so `MmioUart<'a>` is really `MmioUart<'a,
+VolatileBackend>`.
+
+You can implement [`Backend`] yourself, for example to run your driver
+against a host-side mock of the peripheral. A handle using a custom backend
+is created with the generated `new_mmio_with_backend` constructor, and every
+inner block obtained from that handle uses the same backend.
+
+```rust,ignore
+let mut mmio_uart = unsafe { Uart::new_mmio_with_backend::<MyMockBackend>(ptr) };
+// This goes through `MyMockBackend::read_u32`
+let data = mmio_uart.read_data();
+```
+
+A backend only needs to support 8, 16, 32 and 64-bit accesses. Registers of
+other sizes are only supported by backends that override [`Backend::read`]
+and [`Backend::write`], like [`VolatileBackend`] does.
+
+### Testing drivers on the host
+
+With the `testing` feature enabled, the `testing` module provides a
+`RecordingPeripheral`, which keeps a register block in memory and logs every
+access made through its MMIO handles. This allows tests to check the exact
+sequence of register accesses performed by a driver, not just the final
+register contents.
+
+### Linux userspace
+
+With the `std` feature enabled on Linux, the `linux` module maps register
+blocks from a file into the address space of the process, like `/dev/mem`, a
+UIO device or the `resource0` file of a PCI device. This allows drivers running
+in userspace to use the same register block definitions as firmware.
+
+### SVD export
+
+The [`svd`] module formats the CMSIS-SVD `<peripheral>` element of a register
+block, with the registers, their offsets, sizes and access types, for use by
+debuggers and register viewers.
+
+With the `svd-import` feature enabled, `svd::generate` turns a `<peripheral>`
+of an existing SVD file into the source code of `#[derive(Mmio)]` register
+blocks, which can be generated by a build script. This allows register blocks
+provided by a vendor and hand-written ones to be used in the same style.
+
+### C header export
+
+The [`c_header`] module formats a C header with a `typedef volatile struct` of a
+register block, like the one at the top of this page, with `const` read-only
+registers and static assertions of the register offsets. This allows C code
+sharing a peripheral with Rust code to use the same register block definition.
+
 ## Supported attributes
 
 The following attributes are supported for fields with a struct which is wrapped
@@ -341,6 +474,22 @@ with `#[derive(Mmio)]`:
   Requires Rust 1.83.0 or higher.
 - `#[mmio(const_inner)]`: Const getter methods for inner MMIO blocks. Requires Rust 1.83.0 or
   higher.
+- `#[mmio(instances(UART0 = 0x4000_0000, UART1 = 0x4000_1000))]`: Generate a
+  zero-sized handle type with the base address as a const generic parameter, and
+  a type alias for every instance. See [Zero-sized handles](#zero-sized-handles).
+- `#[mmio(address_ranges(0x4000_0000..0x4001_0000))]`: The register block must
+  lie within one of the given address ranges. This is checked by
+  `try_new_mmio_at` at runtime, and for the zero-sized handles at compile time.
+- `#[mmio(singletons)]`: Generate a safe `take()` function for every instance
+  given in `instances`, which returns the handle at most once until it is
+  released again. See [Singletons](#singletons).
+- `#[mmio(endian = "big")]`: All registers of the block are big endian, and are
+  converted to and from the native byte order by the generated functions. This
+  can be overridden for a single field with the `endian` field attribute. Inner
+  blocks are not affected, and need the attribute themselves.
+- `#[mmio(bus_width = 32)]`: The width of the bus of the peripheral in bits, which
+  can be 8, 16, 32 or 64. Fields which are wider than the bus are rejected at
+  compile time, because they can not be accessed atomically.
 
 ### Field attributes
 
@@ -351,14 +500,52 @@ The access permission attributes work for array fields as well.
   to the MMIO handle.
 - `#[mmio(Read)]`: The field can be read, but the read has side effects. The
   generated reader function requires a mutable reference to the MMIO handle.
+- `#[mmio(ClearOnRead)]`: The field can be read, and reading it clears it. The
+  generated reader function requires a mutable reference to the MMIO handle.
+- `#[mmio(Fifo)]`: Every read of the field pops a value from a FIFO. This will
+  generate `pop_${field}()` instead of a reader function, and a
+  `drain_${field}(&mut [T]) -> usize` function which fills the whole buffer by
+  reading repeatedly from the same address, and returns the number of values
+  read. For arrays of FIFO registers, these are `pop_${field}(index)` and
+  `drain_${field}(index, &mut [T]) -> usize`, which select the FIFO to read by
+  its index. For an invalid index, `pop_${field}` returns an
+  [`OutOfBoundsError`], and `drain_${field}` reads nothing and returns 0.
 - `#[mmio(Write)]`: The field can be written to. This will generate a writer
-  function for the field.
+  function for the field. If the field is write-only or a `Fifo`, a
+  `write_${field}_from_slice(&[T])` function which writes every value of a slice
+  to the same address is generated as well.
 - `#[mmio(Modify)]`: The field can be modified. This will generate a modify
-  function for the field which performs a Read-Modify-Write operation.
+  function for the field which performs a Read-Modify-Write operation. It is not
+  allowed on `ClearOnRead` and `Fifo` fields.
+- `#[mmio(W1C)]` and `#[mmio(W1S)]`: Writing a one to a bit of the field clears
+  or sets that bit, while writing a zero leaves it unchanged. This will generate
+  `clear_${field}(mask)` or `set_${field}(mask)` functions. A Read-Modify-Write
+  would clear or set every other bit which reads as one, so `Modify` is not
+  allowed on these fields. Unless specified otherwise, they are `PureRead`.
 - `#[mmio(Inner)]`: The field is a register block. It must be a type which is
   `#[derive(Mmio)]`, which will be verified using trait bounds. The derive macro
   will generate getter functions to retrieve a handle for the inner block, with
   the lifetime of the inner handle tied to the outer handle.
+- `#[mmio(split64(order = "lo_hi", latch))]`: The [`u64`] field is made up of
+  two 32-bit registers, with the low half at the lower address, like a 64-bit
+  timer on a 32-bit bus. The generated functions access the halves in the given
+  `order`, which is `"lo_hi"` (the default) or `"hi_lo"`. Without `latch`, reads
+  do not rely on the hardware latching the second half, and instead read the
+  high half, the low half and the high half again until the high half is
+  stable, which gives a consistent value for a running counter. With `latch`,
+  the halves are read once in the given order. `split64` can be combined with
+  `PureRead`, `Read`, `Write` and `Modify`, and is allowed with
+  `#[mmio(bus_width = 32)]`.
+- `#[mmio(endian = "big")]` or `#[mmio(endian = "little")]`: The byte order of
+  the register, if it differs from the byte order of the register block. Values
+  of big-endian registers, including array elements and bitfield types, are
+  converted to and from the native byte order by every generated function. The
+  high half of a big-endian `split64` register is at the lower address.
+- `#[mmio(offset = 0x1C)]`: The field must be located at the given byte offset
+  from the start of the register block, typically as listed in the datasheet.
+  This is verified at compile time, and can be combined with any of the other
+  field attributes, like `#[mmio(PureRead, offset = 0x1C)]`. It can also be
+  used on reserved fields.
 
 If no permission access modifiers were specified, the library will default to
 `PureRead`, `Write`, `Modify` which is the default for most regular R/W
@@ -368,8 +555,8 @@ registers.
 
 The following field types are supported and tested:
 
-- [`u32`]
-- Arrays of [`u32`]
+- [`u8`], [`u16`], [`u32`] and [`u64`]
+- Arrays of [`u8`], [`u16`], [`u32`] and [`u64`]
 - Bitfields implemented with [`bitbybit::bitfield`]
 - Other `#[derive(Mmio)]` types, if the field is annotated with the
   `#[mmio(Inner)]` attribute. Arrays of inner MMIO types are also allowed.
@@ -380,10 +567,200 @@ Other `repr(transparent)` types should work, but you should be careful to ensure
 that every field corresponds 1:1 with an MMIO register and that they are the
 appropriate size for your CPU architecture.
 
+Registers which are wider than the bus of the peripheral can not be accessed
+atomically. For example, a [`u64`] field on a 32-bit bus compiles into two
+accesses in an unspecified order. The `#[mmio(bus_width = 32)]` outer attribute
+turns such fields into a compile error.
+
 If you accidentally introduce padding (or, if the sum of the size of the
 individual fields isn't the same as the size of the overall `struct`), you will
 get a compile error.
 
+## Zero-sized handles
+
+The `#[mmio(instances(...))]` outer attribute generates a second handle type,
+`Mmio${StructName}At<const BASE: usize, B = VolatileBackend>`, which has the
+same methods as the regular handle but does not store the base address. The
+handle is zero-sized and every register address is a compile time constant, so
+no register is spent on the handle and addresses can be folded into the
+instructions. Each instance gets a type alias, and the `new` constructor checks
+at compile time that the address is non-null and aligned.
+
+```rust,no_run
+#[derive(derive_mmio::Mmio)]
+#[mmio(instances(UART0 = 0x4000_0000, UART1 = 0x4000_1000))]
+#[repr(C)]
+struct Uart {
+    data: u32,
+    status: u32,
+}
+
+fn send(uart: &mut UART1, value: u32) {
+    uart.write_data(value);
+}
+
+assert_eq!(core::mem::size_of::<UART0>(), 0);
+let mut uart1 = unsafe { UART1::new() };
+send(&mut uart1, 0x42);
+```
+
+`MmioUartAt<BASE>` can also be used directly for addresses not listed in the
+attribute. The `as_mmio` method borrows a zero-sized handle as a regular
+`MmioUart` handle, for example to pass it to code which is generic over
+[`RegisterBlock`]. Generic register blocks do not support `instances`.
+
+### Singletons
+
+With `#[mmio(singletons)]`, every instance gets a flag which tracks whether its
+handle was taken. `take()` safely returns a [`Taken`] `MmioUart<'static>` handle
+if it was not taken yet, and [`Taken::release`] gives the instance back. Only the
+handle returned by `take()` can release the instance. Taking a handle for an
+address which is not listed in `instances` fails to compile.
+
+```rust,no_run
+#[derive(derive_mmio::Mmio)]
+#[mmio(no_ctors, instances(UART0 = 0x4000_0000, UART1 = 0x4000_1000), singletons)]
+#[repr(C)]
+struct Uart {
+    data: u32,
+}
+
+let mut uart0 = UART0::take().unwrap();
+assert!(UART0::take().is_none());
+uart0.write_data(0x42);
+uart0.release();
+assert!(UART0::take().is_some());
+```
+
+The flags are atomic booleans by default. On targets without atomic
+compare-and-swap, enable the `critical-section-singletons` feature to update
+the flags within a critical section instead, which requires a
+[`critical-section`] implementation for the target. This is independent of the
+`critical-section` feature, which only adds the `modify_${field}_cs` methods.
+
+[`critical-section`]: https://crates.io/crates/critical-section
+
+## Generic register blocks
+
+Register blocks can have type and const generic parameters, for example to cover
+several variants of the same IP with a different number of channels. The MMIO
+wrapper carries the same generic parameters, after its lifetime and before the
+backend.
+
+```rust
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Channel {
+    control: u32,
+    status: u32,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Dma<const CHANNELS: usize> {
+    control: u32,
+    #[mmio(Inner)]
+    channels: [Channel; CHANNELS],
+}
+
+type Dma4 = Dma<4>;
+
+let mmio_dma: MmioDma<'static, 4> = unsafe { Dma4::new_mmio_at(0x4000_0000) };
+assert_eq!(mmio_dma.len_channels(), 4);
+```
+
+The layout of a generic register block depends on its generic arguments, so
+padding and `#[mmio(offset = ...)]` are checked when a handle is created with one
+of the generated constructors, or when the `REGISTERS` table is used, instead of
+when the structure is defined. Therefore generic register blocks do not support
+`#[mmio(no_ctors)]`. The generic parameter names `B`, `F` and `I` are used by the generated code, and
+lifetime parameters are not supported. The [`mmio_block`] attribute does not
+support generic structs.
+
+## Generating reserved fields
+
+Transcribing a large register map usually means writing a lot of reserved
+fields by hand to fill the gaps between registers. The [`mmio_block`] attribute
+macro can generate them instead. It inserts a reserved field in front of every
+field with an `#[mmio(offset = ...)]` attribute, so you only need to list the
+registers themselves. An optional `size` argument pads the block to a total size,
+which is handy when the block is used in an array of inner blocks.
+
+```rust
+#[derive_mmio::mmio_block(size = 0x40)]
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    data: u32,
+    #[mmio(offset = 0x10)]
+    control: u32,
+    #[mmio(PureRead, offset = 0x2C)]
+    status: u32,
+}
+
+assert_eq!(core::mem::size_of::<Uart>(), 0x40);
+assert_eq!(core::mem::offset_of!(Uart, status), 0x2C);
+```
+
+The attribute must be placed above `#[derive(Mmio)]`, so that the derive sees
+the padded struct. A field placed at an offset that overlaps the previous field,
+or fields which don't fit into the given `size`, are a compile error. The
+generated fields are named `__reservedN` and, like all fields starting with an
+underscore, don't get any accessor methods.
+
+## Register map introspection
+
+Every register block gets a `REGISTERS` associated constant, a table of
+[`RegisterInfo`] listing the name, offset, size, array length, access modifiers
+and inner block of every field except the reserved ones. It is useful for debug
+shells, register dumps and documentation tooling.
+
+```rust
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    data: u32,
+    #[mmio(PureRead)]
+    status: [u32; 2],
+}
+
+for register in Uart::REGISTERS {
+    println!("{:#04x} {} {:?}", register.offset, register.name, register.access);
+}
+assert_eq!(Uart::REGISTERS[1].offset, 4);
+assert_eq!(Uart::REGISTERS[1].len, Some(2));
+assert!(!Uart::REGISTERS[1].access.is_writable());
+```
+
+The same table is available to generic code through
+[`RegisterBlock::REGISTERS`].
+
+## Register snapshots
+
+The generated `snapshot` method reads every register which can be read without
+side effects into a [`Snapshot`], recursing into inner blocks and arrays.
+Registers marked with `#[mmio(Read)]` are skipped. The snapshot prints the
+register names and their values in hex with `{:?}`, and implements
+`defmt::Format` if the `defmt` feature is enabled, which makes it easy to log the
+state of a hung peripheral. Registers marked with `split64` are read in the same
+way as by their `read_${field}` function.
+
+```rust
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    data: u32,
+    #[mmio(Read)]
+    fifo: u32,
+}
+
+let mut uart = Uart { data: 0x42, fifo: 0 };
+let mmio_uart = unsafe { Uart::new_mmio(&raw mut uart) };
+let snapshot = mmio_uart.snapshot();
+assert_eq!(format!("{:?}", snapshot), "Uart { data: 0x00000042 }");
+assert_eq!(snapshot.value("data"), Some(0x42));
+```
+
 ## Additional notes on generated MMIO wrapper
 
 The generated MMIO wrapper type implements the `core::fmt::Debug` trait.
@@ -393,7 +770,28 @@ The generated MMIO wrapper type implements the `core::fmt::Debug` trait.
 #![deny(clippy::doc_markdown)]
 #![deny(missing_docs)]
 
-use core::{fmt::Display, ops::Deref};
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::{
+    fmt::Display,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+pub mod c_header;
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub mod linux;
+mod snapshot;
+pub mod svd;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use snapshot::Snapshot;
+
+#[cfg(feature = "critical-section")]
+#[doc(hidden)]
+pub use critical_section as __critical_section;
 
 /// The error returned when an array access method is given an index that is out
 /// of bounds for the size of the field.
@@ -407,6 +805,115 @@ impl Display for OutOfBoundsError {
     }
 }
 
+/// The error returned when an array access method is given a range of elements
+/// which does not fit into the field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OutOfRangeError {
+    /// The index of the first element of the range.
+    pub start: usize,
+    /// The number of elements in the range.
+    pub len: usize,
+    /// The number of elements in the array.
+    pub array_len: usize,
+}
+
+impl OutOfRangeError {
+    #[doc(hidden)]
+    #[inline]
+    pub const fn check(start: usize, len: usize, array_len: usize) -> Result<(), Self> {
+        if start > array_len || len > array_len - start {
+            return Err(Self {
+                start,
+                len,
+                array_len,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Display for OutOfRangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "out of bounds access at indices {}..{} of an array with {} elements",
+            self.start,
+            self.start.saturating_add(self.len),
+            self.array_len
+        )
+    }
+}
+
+/// The error returned by `try_new_mmio_at` when a register block can not be
+/// located at the given address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum InvalidAddress {
+    /// The address is null.
+    Null,
+    /// The address is not a multiple of the alignment of the register block.
+    Misaligned {
+        /// The given address.
+        addr: usize,
+        /// The alignment of the register block.
+        align: usize,
+    },
+    /// The register block extends past the end of the address space.
+    Overflow(usize),
+    /// The register block does not fit into any of the address ranges given in
+    /// `#[mmio(address_ranges(...))]`.
+    OutOfRange(usize),
+}
+
+impl InvalidAddress {
+    #[doc(hidden)]
+    #[inline]
+    pub const fn check<T>(addr: usize, ranges: &[core::ops::Range<usize>]) -> Result<(), Self> {
+        let align = core::mem::align_of::<T>();
+        if addr == 0 {
+            return Err(Self::Null);
+        }
+        if addr % align != 0 {
+            return Err(Self::Misaligned { addr, align });
+        }
+        let Some(end) = addr.checked_add(core::mem::size_of::<T>()) else {
+            return Err(Self::Overflow(addr));
+        };
+        if ranges.is_empty() {
+            return Ok(());
+        }
+        let mut i = 0;
+        while i < ranges.len() {
+            if addr >= ranges[i].start && end <= ranges[i].end {
+                return Ok(());
+            }
+            i += 1;
+        }
+        Err(Self::OutOfRange(addr))
+    }
+}
+
+impl Display for InvalidAddress {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Null => write!(f, "the address is null"),
+            Self::Misaligned { addr, align } => {
+                write!(f, "the address {addr:#x} is not aligned to {align} bytes")
+            }
+            Self::Overflow(addr) => {
+                write!(
+                    f,
+                    "the register block at {addr:#x} extends past the end of the address space"
+                )
+            }
+            Self::OutOfRange(addr) => {
+                write!(f, "the address {addr:#x} is outside of the allowed ranges")
+            }
+        }
+    }
+}
+
 /// A wrapper type that only gives you shared access to the contents, not
 /// exclusive/mutable access.
 pub struct SharedInner<T>(T);
@@ -434,6 +941,498 @@ impl<T> Deref for SharedInner<T> {
 #[rustversion::since(1.81)]
 impl core::error::Error for OutOfBoundsError {}
 
+#[rustversion::since(1.81)]
+impl core::error::Error for OutOfRangeError {}
+
+#[rustversion::since(1.81)]
+impl core::error::Error for InvalidAddress {}
+
+/// A flag which tracks whether a peripheral instance is taken, used by the
+/// generated `take` function of `#[mmio(singletons)]` and by [`Taken`].
+///
+/// The flag uses an atomic swap, or a critical section if the
+/// `critical-section-singletons` feature is enabled, for targets without atomic
+/// read-modify-write operations.
+#[doc(hidden)]
+pub struct TakenFlag(core::sync::atomic::AtomicBool);
+
+impl TakenFlag {
+    /// Create a flag for an instance which is not taken.
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self(core::sync::atomic::AtomicBool::new(false))
+    }
+
+    /// Mark the instance as taken. Returns `false` if it was taken already.
+    #[cfg(feature = "critical-section-singletons")]
+    pub fn take(&self) -> bool {
+        use core::sync::atomic::Ordering;
+        critical_section::with(|_| {
+            let taken = self.0.load(Ordering::Relaxed);
+            self.0.store(true, Ordering::Relaxed);
+            !taken
+        })
+    }
+
+    /// Mark the instance as taken. Returns `false` if it was taken already.
+    #[cfg(not(feature = "critical-section-singletons"))]
+    pub fn take(&self) -> bool {
+        !self.0.swap(true, core::sync::atomic::Ordering::Acquire)
+    }
+
+    /// Mark the instance as no longer taken.
+    pub fn release(&self) {
+        self.0.store(false, core::sync::atomic::Ordering::Release)
+    }
+}
+
+/// An MMIO handle taken with the generated `take` function of
+/// `#[mmio(singletons)]`.
+///
+/// The handle is accessed through [`Deref`] and [`DerefMut`]. Only a `Taken`
+/// handle can give the instance back with [`Taken::release`], so a handle created
+/// with one of the unsafe constructors can't release an instance which is still
+/// taken.
+pub struct Taken<T> {
+    handle: T,
+    flag: &'static TakenFlag,
+}
+
+impl<T> Taken<T> {
+    /// Wrap a handle which was taken by setting the given flag.
+    ///
+    /// # Safety
+    ///
+    /// The flag must belong to the instance the handle points to, and must have
+    /// been set by this caller.
+    #[doc(hidden)]
+    pub const unsafe fn __new_internal(handle: T, flag: &'static TakenFlag) -> Self {
+        Self { handle, flag }
+    }
+
+    /// Release the handle, so that the instance can be taken again.
+    pub fn release(self) {
+        self.flag.release()
+    }
+}
+
+impl<T> Deref for Taken<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.handle
+    }
+}
+
+impl<T> DerefMut for Taken<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.handle
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for Taken<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Taken").field(&self.handle).finish()
+    }
+}
+
+/// The mechanism used by an MMIO handle to actually access its registers.
+///
+/// Every generated read and write method goes through the backend of the MMIO
+/// handle. The pointer passed to each function is the address of the register,
+/// which is the base address of the MMIO handle plus the offset of the register
+/// within the register block.
+///
+/// The default backend is [`VolatileBackend`], which performs volatile reads
+/// and writes. You can implement this trait yourself, for example to redirect
+/// all accesses to a host-side mock of your peripheral.
+pub trait Backend {
+    /// Read an 8-bit value from the given address.
+    ///
+    /// # Safety
+    ///
+    /// The address must be valid for this backend.
+    unsafe fn read_u8(ptr: *const u8) -> u8;
+
+    /// Read a 16-bit value from the given address.
+    ///
+    /// # Safety
+    ///
+    /// The address must be valid for this backend.
+    unsafe fn read_u16(ptr: *const u16) -> u16;
+
+    /// Read a 32-bit value from the given address.
+    ///
+    /// # Safety
+    ///
+    /// The address must be valid for this backend.
+    unsafe fn read_u32(ptr: *const u32) -> u32;
+
+    /// Read a 64-bit value from the given address.
+    ///
+    /// # Safety
+    ///
+    /// The address must be valid for this backend.
+    unsafe fn read_u64(ptr: *const u64) -> u64;
+
+    /// Write an 8-bit value to the given address.
+    ///
+    /// # Safety
+    ///
+    /// The address must be valid for this backend.
+    unsafe fn write_u8(ptr: *mut u8, value: u8);
+
+    /// Write a 16-bit value to the given address.
+    ///
+    /// # Safety
+    ///
+    /// The address must be valid for this backend.
+    unsafe fn write_u16(ptr: *mut u16, value: u16);
+
+    /// Write a 32-bit value to the given address.
+    ///
+    /// # Safety
+    ///
+    /// The address must be valid for this backend.
+    unsafe fn write_u32(ptr: *mut u32, value: u32);
+
+    /// Write a 64-bit value to the given address.
+    ///
+    /// # Safety
+    ///
+    /// The address must be valid for this backend.
+    unsafe fn write_u64(ptr: *mut u64, value: u64);
+
+    /// Read a register of any type from the given address.
+    ///
+    /// The default implementation dispatches to one of the fixed-width read
+    /// functions based on the size of `T`. Using it with a `T` which is not 1,
+    /// 2, 4 or 8 bytes in size fails to compile.
+    ///
+    /// # Safety
+    ///
+    /// The address must be valid for this backend, and every bit pattern
+    /// returned by the backend must be valid for `T`.
+    #[inline(always)]
+    unsafe fn read<T>(ptr: *const T) -> T {
+        const { assert!(is_register_size(core::mem::size_of::<T>())) };
+        unsafe {
+            match core::mem::size_of::<T>() {
+                1 => core::mem::transmute_copy(&Self::read_u8(ptr.cast())),
+                2 => core::mem::transmute_copy(&Self::read_u16(ptr.cast())),
+                4 => core::mem::transmute_copy(&Self::read_u32(ptr.cast())),
+                8 => core::mem::transmute_copy(&Self::read_u64(ptr.cast())),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    /// Write a register of any type to the given address.
+    ///
+    /// The default implementation dispatches to one of the fixed-width write
+    /// functions based on the size of `T`. Using it with a `T` which is not 1,
+    /// 2, 4 or 8 bytes in size fails to compile.
+    ///
+    /// # Safety
+    ///
+    /// The address must be valid for this backend.
+    #[inline(always)]
+    unsafe fn write<T>(ptr: *mut T, value: T) {
+        const { assert!(is_register_size(core::mem::size_of::<T>())) };
+        let value = core::mem::ManuallyDrop::new(value);
+        unsafe {
+            match core::mem::size_of::<T>() {
+                1 => Self::write_u8(ptr.cast(), core::mem::transmute_copy(&value)),
+                2 => Self::write_u16(ptr.cast(), core::mem::transmute_copy(&value)),
+                4 => Self::write_u32(ptr.cast(), core::mem::transmute_copy(&value)),
+                8 => Self::write_u64(ptr.cast(), core::mem::transmute_copy(&value)),
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+/// Whether a register of the given size in bytes can be accessed through the
+/// fixed-width functions of a [`Backend`].
+const fn is_register_size(size: usize) -> bool {
+    matches!(size, 1 | 2 | 4 | 8)
+}
+
+/// The default [`Backend`], which performs volatile reads and writes through
+/// raw pointers.
+///
+/// Registers of any size are supported by this backend.
+#[derive(Debug, Clone, Copy)]
+pub struct VolatileBackend;
+
+impl Backend for VolatileBackend {
+    #[inline(always)]
+    unsafe fn read_u8(ptr: *const u8) -> u8 {
+        unsafe { ptr.read_volatile() }
+    }
+
+    #[inline(always)]
+    unsafe fn read_u16(ptr: *const u16) -> u16 {
+        unsafe { ptr.read_volatile() }
+    }
+
+    #[inline(always)]
+    unsafe fn read_u32(ptr: *const u32) -> u32 {
+        unsafe { ptr.read_volatile() }
+    }
+
+    #[inline(always)]
+    unsafe fn read_u64(ptr: *const u64) -> u64 {
+        unsafe { ptr.read_volatile() }
+    }
+
+    #[inline(always)]
+    unsafe fn write_u8(ptr: *mut u8, value: u8) {
+        unsafe { ptr.write_volatile(value) }
+    }
+
+    #[inline(always)]
+    unsafe fn write_u16(ptr: *mut u16, value: u16) {
+        unsafe { ptr.write_volatile(value) }
+    }
+
+    #[inline(always)]
+    unsafe fn write_u32(ptr: *mut u32, value: u32) {
+        unsafe { ptr.write_volatile(value) }
+    }
+
+    #[inline(always)]
+    unsafe fn write_u64(ptr: *mut u64, value: u64) {
+        unsafe { ptr.write_volatile(value) }
+    }
+
+    #[inline(always)]
+    unsafe fn read<T>(ptr: *const T) -> T {
+        unsafe { ptr.read_volatile() }
+    }
+
+    #[inline(always)]
+    unsafe fn write<T>(ptr: *mut T, value: T) {
+        unsafe { ptr.write_volatile(value) }
+    }
+}
+
+/// A [`Backend`] for big-endian registers, which converts every value read or
+/// written through the backend `B` between big endian and the native byte order.
+///
+/// The [`Mmio`] derive macro accesses fields marked with `#[mmio(endian = "big")]`
+/// through this backend. Only registers which are 1, 2, 4 or 8 bytes in size are
+/// supported.
+pub struct BigEndian<B>(PhantomData<B>);
+
+impl<B: Backend> Backend for BigEndian<B> {
+    #[inline(always)]
+    unsafe fn read_u8(ptr: *const u8) -> u8 {
+        unsafe { B::read_u8(ptr) }
+    }
+
+    #[inline(always)]
+    unsafe fn read_u16(ptr: *const u16) -> u16 {
+        u16::from_be(unsafe { B::read_u16(ptr) })
+    }
+
+    #[inline(always)]
+    unsafe fn read_u32(ptr: *const u32) -> u32 {
+        u32::from_be(unsafe { B::read_u32(ptr) })
+    }
+
+    #[inline(always)]
+    unsafe fn read_u64(ptr: *const u64) -> u64 {
+        u64::from_be(unsafe { B::read_u64(ptr) })
+    }
+
+    #[inline(always)]
+    unsafe fn write_u8(ptr: *mut u8, value: u8) {
+        unsafe { B::write_u8(ptr, value) }
+    }
+
+    #[inline(always)]
+    unsafe fn write_u16(ptr: *mut u16, value: u16) {
+        unsafe { B::write_u16(ptr, value.to_be()) }
+    }
+
+    #[inline(always)]
+    unsafe fn write_u32(ptr: *mut u32, value: u32) {
+        unsafe { B::write_u32(ptr, value.to_be()) }
+    }
+
+    #[inline(always)]
+    unsafe fn write_u64(ptr: *mut u64, value: u64) {
+        unsafe { B::write_u64(ptr, value.to_be()) }
+    }
+}
+
+/// A register block which has been annotated with `#[derive(Mmio)]`.
+///
+/// This trait is implemented by the [`Mmio`] derive macro. It allows generic code to
+/// create MMIO handles for any register block.
+pub trait RegisterBlock: Sized {
+    /// The generated MMIO handle type for this register block, called
+    /// `Mmio${StructName}`.
+    type Mmio<'a, B>;
+
+    /// Create a new handle to this register block which performs all register
+    /// accesses through the backend `B`.
+    ///
+    /// # Safety
+    ///
+    /// The pointer given must have suitable alignment, and point to an object
+    /// which matches the layout given by the structure pointed to. The pointer must
+    /// also be valid for the chosen backend, for as long as the lifetime `'a` of
+    /// the handle.
+    unsafe fn new_mmio_with_backend<'a, B: Backend>(ptr: *mut Self) -> Self::Mmio<'a, B>;
+
+    /// Description of all registers in this register block, ordered by offset.
+    ///
+    /// This is the same table as the `REGISTERS` associated constant generated on
+    /// the structure itself.
+    const REGISTERS: &'static [RegisterInfo];
+
+    /// The base pointer of an MMIO handle.
+    #[doc(hidden)]
+    fn __mmio_ptr<'a, B>(mmio: &Self::Mmio<'a, B>) -> *mut Self;
+}
+
+/// Description of a register, or an array of registers, in a register block.
+///
+/// The [`Mmio`] derive macro generates a table of these for every register
+/// block, available as the `REGISTERS` associated constant. Reserved fields are
+/// not part of the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterInfo {
+    /// The name of the field.
+    pub name: &'static str,
+    /// The offset of the field from the start of the register block, in bytes.
+    pub offset: usize,
+    /// The size of the register in bytes. For arrays, this is the size of a
+    /// single element.
+    pub size: usize,
+    /// The number of elements if the field is an array.
+    pub len: Option<usize>,
+    /// The access modifiers of the field. This is empty for inner blocks.
+    pub access: RegisterAccess,
+    /// The register block type if the field is marked with `#[mmio(Inner)]`.
+    pub inner: Option<InnerBlockInfo>,
+}
+
+impl RegisterInfo {
+    /// The size of the whole field in bytes, including all elements of an array.
+    pub const fn total_size(&self) -> usize {
+        match self.len {
+            Some(len) => self.size * len,
+            None => self.size,
+        }
+    }
+}
+
+/// Description of an inner register block, see [`RegisterInfo::inner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InnerBlockInfo {
+    /// The name of the register block type.
+    pub name: &'static str,
+    /// The registers of the inner block, with offsets relative to the start of
+    /// the inner block.
+    pub registers: &'static [RegisterInfo],
+}
+
+/// The access modifiers of a register, as a set of flags.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct RegisterAccess(u16);
+
+impl RegisterAccess {
+    /// No access, used for inner blocks.
+    pub const NONE: Self = Self(0);
+    /// The register was marked with `#[mmio(PureRead)]`.
+    pub const PURE_READ: Self = Self(1 << 0);
+    /// The register was marked with `#[mmio(Read)]`.
+    pub const READ: Self = Self(1 << 1);
+    /// The register was marked with `#[mmio(Write)]`.
+    pub const WRITE: Self = Self(1 << 2);
+    /// The register was marked with `#[mmio(Modify)]`.
+    pub const MODIFY: Self = Self(1 << 3);
+    /// The register was marked with `#[mmio(W1C)]`.
+    pub const W1C: Self = Self(1 << 4);
+    /// The register was marked with `#[mmio(W1S)]`.
+    pub const W1S: Self = Self(1 << 5);
+    /// The register was marked with `#[mmio(ClearOnRead)]`.
+    pub const CLEAR_ON_READ: Self = Self(1 << 6);
+    /// The register was marked with `#[mmio(Fifo)]`.
+    pub const FIFO: Self = Self(1 << 7);
+    /// The register was marked with `#[mmio(split64)]`, and is accessed as two
+    /// 32-bit halves.
+    pub const SPLIT64: Self = Self(1 << 8);
+    /// The register was marked with `#[mmio(endian = "big")]`, or is part of a
+    /// register block marked with it.
+    pub const BIG_ENDIAN: Self = Self(1 << 9);
+    /// The register was marked with `#[mmio(split64(order = "hi_lo"))]`, and the
+    /// high half is accessed first.
+    pub const SPLIT64_HI_FIRST: Self = Self(1 << 10);
+    /// The register was marked with `#[mmio(split64(latch))]`, and the hardware
+    /// latches the second half when the first half is read.
+    pub const SPLIT64_LATCH: Self = Self(1 << 11);
+
+    const NAMES: [(Self, &'static str); 12] = [
+        (Self::PURE_READ, "PureRead"),
+        (Self::READ, "Read"),
+        (Self::CLEAR_ON_READ, "ClearOnRead"),
+        (Self::FIFO, "Fifo"),
+        (Self::WRITE, "Write"),
+        (Self::MODIFY, "Modify"),
+        (Self::W1C, "W1C"),
+        (Self::W1S, "W1S"),
+        (Self::SPLIT64, "Split64"),
+        (Self::SPLIT64_HI_FIRST, "Split64HiFirst"),
+        (Self::SPLIT64_LATCH, "Split64Latch"),
+        (Self::BIG_ENDIAN, "BigEndian"),
+    ];
+
+    /// Combine two sets of access modifiers.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Check whether all access modifiers in `other` are also set in `self`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Check whether the register can be read, with or without side effects.
+    ///
+    /// This includes clear-on-read and FIFO registers.
+    pub const fn is_readable(self) -> bool {
+        self.0 & (Self::PURE_READ.0 | Self::READ.0 | Self::CLEAR_ON_READ.0 | Self::FIFO.0) != 0
+    }
+
+    /// Check whether the register can be written.
+    pub const fn is_writable(self) -> bool {
+        self.contains(Self::WRITE)
+    }
+}
+
+impl core::fmt::Debug for RegisterAccess {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut first = true;
+        for (flag, name) in Self::NAMES {
+            if self.contains(flag) {
+                if !first {
+                    f.write_str(" | ")?;
+                }
+                f.write_str(name)?;
+                first = false;
+            }
+        }
+        if first {
+            f.write_str("NONE")?;
+        }
+        Ok(())
+    }
+}
+
 /// Marker trait to check whether an inner field's type has been marked with
 /// `#[derive(Mmio)]`.
 ///
@@ -448,4 +1447,4 @@ pub unsafe trait _MmioMarker {}
 pub const fn is_mmio<M: _MmioMarker>() {}
 
 #[doc(inline)]
-pub use derive_mmio_macro::Mmio;
+pub use derive_mmio_macro::{mmio_block, Mmio};
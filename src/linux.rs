@@ -0,0 +1,150 @@
+//! Access to register blocks from Linux userspace.
+//!
+//! This module is only available with the `std` feature enabled, on Linux.
+//!
+//! A [`Mapping`] maps a register block from a file into the address space of
+//! the process, for example from `/dev/mem`, a UIO device like `/dev/uio0` or
+//! the `resource0` file of a PCI device in sysfs. This allows drivers running in
+//! userspace to use the same register block definitions as firmware.
+//!
+//! ```rust,no_run
+//! use derive_mmio::linux::Mapping;
+//!
+//! #[derive(derive_mmio::Mmio)]
+//! #[repr(C)]
+//! struct Uart {
+//!     data: u32,
+//!     status: u32,
+//! }
+//!
+//! // Safety: The UART is at physical address 0x4000_0000
+//! let mut uart = unsafe { Mapping::<Uart>::open("/dev/mem", 0x4000_0000) }?;
+//! let status = uart.read_status();
+//! uart.write_data(0x42);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! The memory is unmapped when the [`Mapping`] is dropped.
+
+use core::{
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+};
+use std::{fs::File, io, os::unix::fs::OpenOptionsExt, path::Path};
+
+use memmap2::{MmapOptions, MmapRaw};
+
+use crate::{Backend, InvalidAddress, RegisterBlock, VolatileBackend};
+
+/// A register block mapped from a file, which unmaps the memory when dropped.
+///
+/// The mapping dereferences to the MMIO handle of the register block. The
+/// handle is only reachable through a borrow of the mapping, so it can't outlive
+/// the mapping. If the handle is swapped with the handle of another mapping
+/// through [`DerefMut`], the memory is not unmapped when the mapping is dropped,
+/// since the other mapping may still use it.
+pub struct Mapping<T: RegisterBlock, B: Backend = VolatileBackend> {
+    handle: T::Mmio<'static, B>,
+    ptr: *mut T,
+    mmap: ManuallyDrop<MmapRaw>,
+}
+
+impl<T: RegisterBlock> Mapping<T> {
+    /// Open the file at `path` and map the register block located at `offset`.
+    ///
+    /// The file is opened for reading and writing with `O_SYNC`, which makes
+    /// `/dev/mem` map the memory uncached. For UIO devices, the offset selects the
+    /// memory map: map `N` is located at offset `N` times the page size.
+    ///
+    /// # Safety
+    ///
+    /// See the safety notes for [`Mapping::new`].
+    pub unsafe fn open<P: AsRef<Path>>(path: P, offset: u64) -> io::Result<Self> {
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .custom_flags(libc::O_SYNC)
+            .open(path)?;
+        Self::new(&file, offset)
+    }
+
+    /// Map the register block located at `offset` in the given file.
+    ///
+    /// The file must be open for reading and writing. It can be closed once the
+    /// mapping was created.
+    ///
+    /// # Safety
+    ///
+    /// The file must contain an instance of the register block at the given
+    /// offset, and must not be truncated while it is mapped. See also the safety
+    /// notes of the `new_mmio` constructors regarding multiple handles to the
+    /// same register block.
+    pub unsafe fn new(file: &File, offset: u64) -> io::Result<Self> {
+        Self::with_backend(file, offset)
+    }
+}
+
+impl<T: RegisterBlock, B: Backend> Mapping<T, B> {
+    /// Map the register block located at `offset` in the given file, with a
+    /// handle which performs all register accesses through the backend `B`.
+    ///
+    /// # Safety
+    ///
+    /// See the safety notes for [`Mapping::new`].
+    pub unsafe fn with_backend(file: &File, offset: u64) -> io::Result<Self> {
+        let mmap = MmapOptions::new()
+            .offset(offset)
+            .len(core::mem::size_of::<T>())
+            .map_raw(file)?;
+        let ptr = mmap.as_mut_ptr().cast::<T>();
+        InvalidAddress::check::<T>(ptr.addr(), &[])
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        Ok(Self {
+            // Safety: The pointer was checked to be aligned, and the memory stays
+            // mapped for as long as the handle points to it, see `drop`
+            handle: unsafe { T::new_mmio_with_backend(ptr) },
+            ptr,
+            mmap: ManuallyDrop::new(mmap),
+        })
+    }
+
+    /// Get a pointer to the mapped register block.
+    pub fn as_ptr(&self) -> *mut T {
+        self.ptr
+    }
+}
+
+impl<T: RegisterBlock, B: Backend> Deref for Mapping<T, B> {
+    type Target = T::Mmio<'static, B>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.handle
+    }
+}
+
+impl<T: RegisterBlock, B: Backend> DerefMut for Mapping<T, B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.handle
+    }
+}
+
+impl<T: RegisterBlock, B: Backend> Drop for Mapping<T, B> {
+    fn drop(&mut self) {
+        // Handles can only be swapped, not copied, so if the handle still points
+        // to this mapping, no other handle does. Otherwise the memory is leaked.
+        if core::ptr::eq(T::__mmio_ptr(&self.handle), self.ptr) {
+            // Safety: The memory is not used afterwards
+            unsafe { ManuallyDrop::drop(&mut self.mmap) }
+        }
+    }
+}
+
+impl<T: RegisterBlock, B: Backend> core::fmt::Debug for Mapping<T, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Mapping({:p})", self.ptr)
+    }
+}
+
+// Safety: The mapping can be sent to another thread if the register block can,
+// just like the MMIO handle.
+unsafe impl<T: RegisterBlock + Send, B: Backend> Send for Mapping<T, B> {}
@@ -0,0 +1,382 @@
+//! Snapshots of the readable registers of a register block.
+
+use core::{fmt, mem::MaybeUninit};
+
+use crate::{Backend, BigEndian, RegisterAccess, RegisterBlock, RegisterInfo};
+
+/// The values of all registers of a register block which can be read without side
+/// effects, captured at one point in time.
+///
+/// This is created with the generated `snapshot` method of an MMIO handle. Only
+/// registers marked with `#[mmio(PureRead)]` (the default) are read, including the
+/// ones of inner blocks and arrays. Registers marked with `#[mmio(Read)]` are
+/// skipped, because reading them has side effects. Taking a snapshot of a register
+/// block with pure readable registers which are not 1, 2, 4 or 8 bytes in size
+/// fails to compile.
+///
+/// The [`Debug`](core::fmt::Debug) implementation (and the `defmt::Format`
+/// implementation if the `defmt` feature is enabled) prints the register names and
+/// their values in hex.
+pub struct Snapshot<T: RegisterBlock> {
+    /// Register values at the same offsets as in the register block. Registers which
+    /// were not read are left zeroed.
+    data: MaybeUninit<T>,
+}
+
+impl<T: RegisterBlock> Snapshot<T> {
+    /// Read all pure readable registers of the register block at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// The pointer given must have suitable alignment, and point to an object
+    /// which matches the layout given by the structure pointed to. The pointer must
+    /// also be valid for the backend `B`.
+    pub unsafe fn read<B: Backend>(ptr: *const T) -> Self {
+        const {
+            assert!(
+                has_register_sizes(T::REGISTERS),
+                "snapshots only support registers which are 1, 2, 4 or 8 bytes in size"
+            )
+        };
+        let mut data = MaybeUninit::<T>::zeroed();
+        // Safety: The zeroed data is initialized, and `T` has no padding
+        let bytes = unsafe {
+            core::slice::from_raw_parts_mut(data.as_mut_ptr().cast(), core::mem::size_of::<T>())
+        };
+        // Safety: Upheld by the caller
+        unsafe { read_registers::<B>(T::REGISTERS, ptr.cast(), bytes) };
+        Self { data }
+    }
+
+    /// The value of a register, given by its name. Registers of inner blocks are
+    /// named like `bank.status`, and array elements like `array[1]`.
+    ///
+    /// Returns `None` if there is no such register, or if it could not be read
+    /// without side effects.
+    pub fn value(&self, register: &str) -> Option<u64> {
+        find_value(T::REGISTERS, self.bytes(), register)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        // Safety: The data was zero initialized, and `T` has no padding
+        unsafe { core::slice::from_raw_parts(self.data.as_ptr().cast(), core::mem::size_of::<T>()) }
+    }
+
+    /// The name of `T` without its module path and generic arguments.
+    fn name() -> &'static str {
+        let name = core::any::type_name::<T>();
+        let name = name.split('<').next().unwrap_or(name);
+        name.rsplit("::").next().unwrap_or(name)
+    }
+}
+
+impl<T: RegisterBlock> fmt::Debug for Snapshot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Block {
+            name: Self::name(),
+            registers: T::REGISTERS,
+            bytes: self.bytes(),
+        }
+        .fmt(f)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: RegisterBlock> defmt::Format for Snapshot<T> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        Block {
+            name: Self::name(),
+            registers: T::REGISTERS,
+            bytes: self.bytes(),
+        }
+        .format(f)
+    }
+}
+
+/// Check whether a register is captured in a snapshot
+fn is_captured(register: &RegisterInfo) -> bool {
+    (register.inner.is_some() && register.size != 0)
+        || register.access.contains(RegisterAccess::PURE_READ)
+}
+
+/// Check that all pure readable registers, including the ones of inner blocks,
+/// can be read with a single fixed-width access.
+const fn has_register_sizes(registers: &[RegisterInfo]) -> bool {
+    let mut index = 0;
+    while index < registers.len() {
+        let register = &registers[index];
+        let valid = match register.inner {
+            Some(inner) => has_register_sizes(inner.registers),
+            None => {
+                !register.access.contains(RegisterAccess::PURE_READ)
+                    || matches!(register.size, 1 | 2 | 4 | 8)
+            }
+        };
+        if !valid {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+/// The elements of a register, as `(index, bytes)` pairs.
+fn elements<'a>(
+    register: &RegisterInfo,
+    bytes: &'a [u8],
+) -> impl Iterator<Item = (usize, &'a [u8])> + 'a {
+    let start = register.offset;
+    bytes[start..start + register.total_size()]
+        .chunks_exact(register.size)
+        .enumerate()
+}
+
+/// Read all pure readable registers starting at `base` into `out`.
+unsafe fn read_registers<B: Backend>(registers: &[RegisterInfo], base: *const u8, out: &mut [u8]) {
+    for register in registers.iter().filter(|register| is_captured(register)) {
+        let start = register.offset;
+        let elements = out[start..start + register.total_size()].chunks_exact_mut(register.size);
+        for (index, out) in elements.enumerate() {
+            // Safety: The register is part of the block at `base`, see `Snapshot::read`
+            unsafe {
+                let ptr = base.add(start + index * register.size);
+                match register.inner {
+                    Some(inner) => read_registers::<B>(inner.registers, ptr, out),
+                    None if register.access.contains(RegisterAccess::BIG_ENDIAN) => {
+                        read_value::<BigEndian<B>>(register.access, ptr, out)
+                    }
+                    None => read_value::<B>(register.access, ptr, out),
+                }
+            }
+        }
+    }
+}
+
+/// Read a single register, in halves if it is a `split64` register.
+unsafe fn read_value<B: Backend>(access: RegisterAccess, ptr: *const u8, out: &mut [u8]) {
+    // Safety: Upheld by the caller
+    unsafe {
+        if access.contains(RegisterAccess::SPLIT64) {
+            read_split64::<B>(access, ptr.cast(), out)
+        } else {
+            read_register::<B>(ptr, out)
+        }
+    }
+}
+
+/// Read a single register with a width given by the length of `out`.
+unsafe fn read_register<B: Backend>(ptr: *const u8, out: &mut [u8]) {
+    // Safety: Upheld by the caller
+    unsafe {
+        match out.len() {
+            1 => out.copy_from_slice(&B::read_u8(ptr).to_ne_bytes()),
+            2 => out.copy_from_slice(&B::read_u16(ptr.cast()).to_ne_bytes()),
+            4 => out.copy_from_slice(&B::read_u32(ptr.cast()).to_ne_bytes()),
+            8 => out.copy_from_slice(&B::read_u64(ptr.cast()).to_ne_bytes()),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Read a 64-bit register as two 32-bit halves, like the generated read function.
+///
+/// Latched registers are read once, in the order given by the `split64` options.
+/// Other registers are read high, low and high again, until the high half did not
+/// change while reading the low half. The high half is at the lower address of
+/// big-endian registers.
+unsafe fn read_split64<B: Backend>(access: RegisterAccess, ptr: *const u32, out: &mut [u8]) {
+    // Safety: Upheld by the caller
+    let value = unsafe {
+        let (lo_ptr, hi_ptr) = if access.contains(RegisterAccess::BIG_ENDIAN) {
+            (ptr.add(1), ptr)
+        } else {
+            (ptr, ptr.add(1))
+        };
+        if access.contains(RegisterAccess::SPLIT64_LATCH) {
+            let (hi, lo) = if access.contains(RegisterAccess::SPLIT64_HI_FIRST) {
+                let hi = B::read_u32(hi_ptr);
+                (hi, B::read_u32(lo_ptr))
+            } else {
+                let lo = B::read_u32(lo_ptr);
+                (B::read_u32(hi_ptr), lo)
+            };
+            (u64::from(hi) << 32) | u64::from(lo)
+        } else {
+            loop {
+                let hi = B::read_u32(hi_ptr);
+                let lo = B::read_u32(lo_ptr);
+                if B::read_u32(hi_ptr) == hi {
+                    break (u64::from(hi) << 32) | u64::from(lo);
+                }
+            }
+        }
+    };
+    out.copy_from_slice(&value.to_ne_bytes());
+}
+
+/// Convert the bytes of a register into its value.
+fn to_value(bytes: &[u8]) -> u64 {
+    match bytes.len() {
+        1 => bytes[0].into(),
+        2 => u16::from_ne_bytes(bytes.try_into().unwrap()).into(),
+        4 => u32::from_ne_bytes(bytes.try_into().unwrap()).into(),
+        _ => u64::from_ne_bytes(bytes.try_into().unwrap()),
+    }
+}
+
+fn find_value(registers: &[RegisterInfo], bytes: &[u8], name: &str) -> Option<u64> {
+    let (field, rest) = match name.find('.') {
+        Some(dot) => (&name[..dot], Some(&name[dot + 1..])),
+        None => (name, None),
+    };
+    let (field, index) = match field.strip_suffix(']').and_then(|f| f.split_once('[')) {
+        Some((field, index)) => (field, Some(index.parse::<usize>().ok()?)),
+        None => (field, None),
+    };
+    let register = registers
+        .iter()
+        .find(|register| register.name == field && is_captured(register))?;
+    if index.is_some() != register.len.is_some() {
+        return None;
+    }
+    let (_, bytes) = elements(register, bytes).nth(index.unwrap_or(0))?;
+    match (register.inner, rest) {
+        (Some(inner), Some(rest)) => find_value(inner.registers, bytes, rest),
+        (None, None) => Some(to_value(bytes)),
+        _ => None,
+    }
+}
+
+/// A register block in a snapshot, for formatting.
+struct Block<'a> {
+    name: &'a str,
+    registers: &'static [RegisterInfo],
+    bytes: &'a [u8],
+}
+
+/// A register or an element of a register array in a snapshot, for formatting.
+struct Value<'a> {
+    register: &'static RegisterInfo,
+    bytes: &'a [u8],
+}
+
+/// A register array in a snapshot, for formatting.
+struct Array<'a> {
+    register: &'static RegisterInfo,
+    bytes: &'a [u8],
+}
+
+impl<'a> Block<'a> {
+    fn fields(&self) -> impl Iterator<Item = (&'static str, Field<'a>)> + 'a {
+        let bytes = self.bytes;
+        self.registers
+            .iter()
+            .filter(|register| is_captured(register))
+            .map(move |register| {
+                let field = if register.len.is_some() {
+                    Field::Array(Array { register, bytes })
+                } else {
+                    let (_, bytes) = elements(register, bytes).next().unwrap();
+                    Field::Value(Value { register, bytes })
+                };
+                (register.name, field)
+            })
+    }
+}
+
+enum Field<'a> {
+    Value(Value<'a>),
+    Array(Array<'a>),
+}
+
+impl fmt::Debug for Block<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct(self.name);
+        for (name, field) in self.fields() {
+            match field {
+                Field::Value(value) => debug.field(name, &value),
+                Field::Array(array) => debug.field(name, &array),
+            };
+        }
+        debug.finish()
+    }
+}
+
+impl fmt::Debug for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.register.inner {
+            Some(inner) => Block {
+                name: inner.name,
+                registers: inner.registers,
+                bytes: self.bytes,
+            }
+            .fmt(f),
+            None => write!(
+                f,
+                "{:#0width$x}",
+                to_value(self.bytes),
+                width = 2 + 2 * self.bytes.len()
+            ),
+        }
+    }
+}
+
+impl fmt::Debug for Array<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(elements(self.register, self.bytes).map(|(_, bytes)| Value {
+                register: self.register,
+                bytes,
+            }))
+            .finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Block<'_> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{=str} {{", self.name);
+        for (index, (name, field)) in self.fields().enumerate() {
+            let separator = if index == 0 { " " } else { ", " };
+            defmt::write!(f, "{=str}{=str}: ", separator, name);
+            match field {
+                Field::Value(value) => value.format(f),
+                Field::Array(array) => array.format(f),
+            }
+        }
+        defmt::write!(f, " }}");
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Value<'_> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        match self.register.inner {
+            Some(inner) => Block {
+                name: inner.name,
+                registers: inner.registers,
+                bytes: self.bytes,
+            }
+            .format(f),
+            None => defmt::write!(f, "{=u64:#x}", to_value(self.bytes)),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Array<'_> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "[");
+        for (index, bytes) in elements(self.register, self.bytes) {
+            if index != 0 {
+                defmt::write!(f, ", ");
+            }
+            Value {
+                register: self.register,
+                bytes,
+            }
+            .format(f);
+        }
+        defmt::write!(f, "]");
+    }
+}
@@ -0,0 +1,208 @@
+//! Export of register blocks as CMSIS-SVD.
+//!
+//! A [`Peripheral`] formats the `<peripheral>` element of a CMSIS-SVD file from
+//! the [`RegisterBlock::REGISTERS`] table of a register block. This allows
+//! debuggers and IDE register viewers to show the registers of hand-written
+//! register blocks without maintaining a separate SVD file.
+//!
+//! ```rust
+//! use derive_mmio::svd::Peripheral;
+//!
+//! #[derive(derive_mmio::Mmio)]
+//! #[repr(C)]
+//! struct Uart {
+//!     data: u32,
+//!     #[mmio(PureRead)]
+//!     status: u32,
+//! }
+//!
+//! let svd = Peripheral::<Uart>::new("UART0", 0x4000_0000)
+//!     .description("The first UART")
+//!     .to_string();
+//! assert!(svd.starts_with("<peripheral>\n  <name>UART0</name>\n"));
+//! assert!(svd.contains("<name>status</name>"));
+//! ```
+//!
+//! Registers are described with their offset, size and access. Arrays become
+//! registers with a `<dim>` element, and inner blocks become `<cluster>`
+//! elements. Write-one-to-clear and write-one-to-set registers are described with
+//! `<modifiedWriteValues>`, and registers with read side effects with
+//! `<readAction>`. The byte order is a property of the whole device in SVD, so
+//! `#[mmio(endian = "big")]` is not part of the output.
+//!
+//! With the `svd-import` feature enabled, [`generate`] goes the other way, and
+//! generates the source code of register blocks from a `<peripheral>` of an SVD
+//! file, for use in a build script.
+
+use core::{fmt, marker::PhantomData};
+
+use crate::{RegisterAccess, RegisterBlock, RegisterInfo};
+
+#[cfg(feature = "svd-import")]
+mod import;
+
+#[cfg(feature = "svd-import")]
+pub use import::{generate, ImportError};
+
+/// The CMSIS-SVD `<peripheral>` element of a register block.
+///
+/// The element is written by the [`Display`](core::fmt::Display)
+/// implementation, and can be inserted into the `<peripherals>` element of an
+/// SVD file.
+pub struct Peripheral<'a, T: RegisterBlock> {
+    name: &'a str,
+    base_address: u64,
+    description: Option<&'a str>,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: RegisterBlock> Peripheral<'a, T> {
+    /// Describe an instance of the register block `T` with the given name, at the
+    /// given base address.
+    pub const fn new(name: &'a str, base_address: u64) -> Self {
+        Self {
+            name,
+            base_address,
+            description: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Set the description of the peripheral.
+    pub const fn description(mut self, description: &'a str) -> Self {
+        self.description = Some(description);
+        self
+    }
+}
+
+impl<T: RegisterBlock> fmt::Display for Peripheral<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<peripheral>")?;
+        writeln!(f, "  <name>{}</name>", Escaped(self.name))?;
+        if let Some(description) = self.description {
+            writeln!(f, "  <description>{}</description>", Escaped(description))?;
+        }
+        writeln!(f, "  <baseAddress>{:#x}</baseAddress>", self.base_address)?;
+        writeln!(f, "  <addressBlock>")?;
+        writeln!(f, "    <offset>0x0</offset>")?;
+        writeln!(f, "    <size>{:#x}</size>", core::mem::size_of::<T>())?;
+        writeln!(f, "    <usage>registers</usage>")?;
+        writeln!(f, "  </addressBlock>")?;
+        writeln!(f, "  <registers>")?;
+        write_registers(f, T::REGISTERS, 2)?;
+        writeln!(f, "  </registers>")?;
+        writeln!(f, "</peripheral>")
+    }
+}
+
+/// Write the `<register>` and `<cluster>` elements of a register table.
+fn write_registers(
+    f: &mut fmt::Formatter<'_>,
+    registers: &[RegisterInfo],
+    depth: usize,
+) -> fmt::Result {
+    let indent = Indent(depth);
+    for register in registers {
+        let element = if register.inner.is_some() {
+            "cluster"
+        } else {
+            "register"
+        };
+        writeln!(f, "{indent}<{element}>")?;
+        if let Some(len) = register.len {
+            writeln!(f, "{indent}  <dim>{len}</dim>")?;
+            writeln!(
+                f,
+                "{indent}  <dimIncrement>{:#x}</dimIncrement>",
+                register.size
+            )?;
+            writeln!(f, "{indent}  <name>{}[%s]</name>", register.name)?;
+        } else {
+            writeln!(f, "{indent}  <name>{}</name>", register.name)?;
+        }
+        match register.inner {
+            Some(inner) => {
+                // The description is mandatory for clusters
+                writeln!(f, "{indent}  <description>{}</description>", inner.name)?;
+                writeln!(
+                    f,
+                    "{indent}  <addressOffset>{:#x}</addressOffset>",
+                    register.offset
+                )?;
+                write_registers(f, inner.registers, depth + 1)?;
+            }
+            None => {
+                writeln!(
+                    f,
+                    "{indent}  <addressOffset>{:#x}</addressOffset>",
+                    register.offset
+                )?;
+                writeln!(f, "{indent}  <size>{}</size>", register.size * 8)?;
+                writeln!(f, "{indent}  <access>{}</access>", access(register.access))?;
+                if register.access.contains(RegisterAccess::W1C) {
+                    writeln!(
+                        f,
+                        "{indent}  <modifiedWriteValues>oneToClear</modifiedWriteValues>"
+                    )?;
+                } else if register.access.contains(RegisterAccess::W1S) {
+                    writeln!(
+                        f,
+                        "{indent}  <modifiedWriteValues>oneToSet</modifiedWriteValues>"
+                    )?;
+                }
+                if register.access.contains(RegisterAccess::CLEAR_ON_READ) {
+                    writeln!(f, "{indent}  <readAction>clear</readAction>")?;
+                } else if register.access.contains(RegisterAccess::READ)
+                    || register.access.contains(RegisterAccess::FIFO)
+                {
+                    writeln!(f, "{indent}  <readAction>modify</readAction>")?;
+                }
+            }
+        }
+        writeln!(f, "{indent}</{element}>")?;
+    }
+    Ok(())
+}
+
+/// The SVD access type of a register.
+fn access(access: RegisterAccess) -> &'static str {
+    let writable = access.is_writable()
+        || access.contains(RegisterAccess::W1C)
+        || access.contains(RegisterAccess::W1S);
+    match (access.is_readable(), writable) {
+        (true, false) => "read-only",
+        (false, true) => "write-only",
+        _ => "read-write",
+    }
+}
+
+/// Indentation by two spaces per level.
+struct Indent(usize);
+
+impl fmt::Display for Indent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for _ in 0..self.0 {
+            f.write_str("  ")?;
+        }
+        Ok(())
+    }
+}
+
+/// Text with the XML special characters escaped.
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&apos;")?,
+                c => fmt::Write::write_char(f, c)?,
+            }
+        }
+        Ok(())
+    }
+}
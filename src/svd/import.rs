@@ -0,0 +1,494 @@
+//! Generation of register blocks from CMSIS-SVD files.
+
+use std::{
+    borrow::ToOwned,
+    fmt::{self, Write},
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use roxmltree::{Document, Node};
+
+/// The error returned by [`generate`] when the SVD file can not be converted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportError(String);
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+fn error<T>(message: impl Into<String>) -> Result<T, ImportError> {
+    Err(ImportError(message.into()))
+}
+
+/// Generate the source code of `#[derive(Mmio)]` register blocks for one
+/// `<peripheral>` of a CMSIS-SVD file.
+///
+/// The peripheral becomes a `#[repr(C)]` structure named after its
+/// `<headerStructName>` or `<name>`, and every `<cluster>` becomes a structure of
+/// its own, used as an `#[mmio(Inner)]` field. Gaps between registers are filled
+/// with reserved `_reservedN` fields, and every field is annotated with its
+/// offset, which is checked at compile time. The access type, the
+/// `<modifiedWriteValues>` and the `<readAction>` of a register are translated to
+/// the matching `#[mmio(...)]` access attributes.
+///
+/// This is meant to be called from a build script, with the generated code
+/// written to a file in `OUT_DIR` which is included with [`include!`].
+///
+/// ```rust
+/// let svd = r#"
+/// <device>
+///   <peripherals>
+///     <peripheral>
+///       <name>UART0</name>
+///       <baseAddress>0x40000000</baseAddress>
+///       <size>32</size>
+///       <registers>
+///         <register>
+///           <name>DATA</name>
+///           <addressOffset>0x0</addressOffset>
+///         </register>
+///         <register>
+///           <name>STATUS</name>
+///           <addressOffset>0x8</addressOffset>
+///           <access>read-only</access>
+///         </register>
+///       </registers>
+///     </peripheral>
+///   </peripherals>
+/// </device>
+/// "#;
+/// let code = derive_mmio::svd::generate(svd, "UART0").unwrap();
+/// assert!(code.contains("pub struct Uart0 {"));
+/// assert!(code.contains("#[mmio(PureRead, offset = 0x8)]\n    status: u32,"));
+/// ```
+///
+/// Registers which overlap, for example alternate registers, are not supported.
+/// Arrays of registers and clusters are supported if their elements are adjacent,
+/// and are expanded into separate fields if the name has no `[%s]` suffix.
+pub fn generate(svd: &str, peripheral: &str) -> Result<String, ImportError> {
+    let document =
+        Document::parse(svd).map_err(|e| ImportError(format!("invalid SVD file: {e}")))?;
+    let root = document.root_element();
+    let peripherals = child(root, "peripherals")
+        .ok_or_else(|| ImportError("the SVD file has no peripherals".to_owned()))?;
+    let find = |name: &str| {
+        peripherals
+            .children()
+            .find(|node| node.has_tag_name("peripheral") && text(*node, "name") == Some(name))
+    };
+    let Some(node) = find(peripheral) else {
+        return error(format!("there is no peripheral named `{peripheral}`"));
+    };
+    let mut registers = child(node, "registers");
+    let mut description = description_of(node);
+    if let Some(base) = node.attribute("derivedFrom") {
+        let Some(base) = find(base) else {
+            return error(format!("there is no peripheral named `{base}`"));
+        };
+        registers = registers.or_else(|| child(base, "registers"));
+        description = description.or_else(|| description_of(base));
+    }
+    let Some(registers) = registers else {
+        return error(format!("the peripheral `{peripheral}` has no registers"));
+    };
+
+    let properties = Properties::default()
+        .inherit(root)?
+        .inherit(peripherals)?
+        .inherit(node)?;
+    let name = text(node, "headerStructName").unwrap_or(peripheral);
+    let mut blocks = Vec::new();
+    let block = Block::parse(
+        type_name(name),
+        description,
+        registers,
+        properties,
+        &mut blocks,
+    )?;
+    if blocks.iter().any(|b| b.name == block.name) {
+        return error(format!(
+            "the peripheral and a cluster are both named `{}`",
+            block.name
+        ));
+    }
+    blocks.insert(0, block);
+
+    let mut code = String::new();
+    for block in &blocks {
+        if !code.is_empty() {
+            code.push('\n');
+        }
+        block.write(&mut code).unwrap();
+    }
+    Ok(code)
+}
+
+/// The register properties, which are inherited from the enclosing elements.
+#[derive(Clone, Copy, Default)]
+struct Properties<'a> {
+    size: Option<u64>,
+    access: Option<&'a str>,
+}
+
+impl<'a> Properties<'a> {
+    fn inherit(self, node: Node<'a, '_>) -> Result<Self, ImportError> {
+        Ok(Self {
+            size: match text(node, "size") {
+                Some(size) => Some(number(size)?),
+                None => self.size,
+            },
+            access: text(node, "access").or(self.access),
+        })
+    }
+}
+
+/// A register block structure.
+struct Block {
+    name: String,
+    description: Option<String>,
+    fields: Vec<Field>,
+    size: u64,
+    align: u64,
+}
+
+/// A field of a register block, which is a register, an inner block, or an
+/// array of either.
+#[derive(PartialEq)]
+struct Field {
+    name: String,
+    description: Option<String>,
+    offset: u64,
+    ty: String,
+    access: Vec<&'static str>,
+    element_size: u64,
+    align: u64,
+    len: Option<u64>,
+}
+
+impl Field {
+    fn size(&self) -> u64 {
+        self.element_size * self.len.unwrap_or(1)
+    }
+}
+
+impl Block {
+    /// Parse the registers and clusters in `node`. The blocks of the clusters are
+    /// added to `blocks`.
+    fn parse(
+        name: String,
+        description: Option<String>,
+        node: Node,
+        properties: Properties,
+        blocks: &mut Vec<Block>,
+    ) -> Result<Self, ImportError> {
+        let mut fields = Vec::new();
+        for node in node.children().filter(Node::is_element) {
+            let properties = properties.inherit(node)?;
+            let Some(svd_name) = text(node, "name") else {
+                continue;
+            };
+            let offset = number(text(node, "addressOffset").unwrap_or("0"))?;
+            let (ty, access, element_size, align) = match node.tag_name().name() {
+                "register" => {
+                    let Some(bits) = properties.size else {
+                        return error(format!("the register `{svd_name}` has no size"));
+                    };
+                    let ty = match bits {
+                        8 => "u8",
+                        16 => "u16",
+                        32 => "u32",
+                        64 => "u64",
+                        _ => return error(format!("the register `{svd_name}` has {bits} bits")),
+                    };
+                    (ty.to_owned(), access(node, properties)?, bits / 8, bits / 8)
+                }
+                "cluster" => {
+                    let struct_name = text(node, "headerStructName").unwrap_or(svd_name);
+                    let mut block = Block::parse(
+                        type_name(struct_name),
+                        description_of(node),
+                        node,
+                        properties,
+                        blocks,
+                    )?;
+                    if let Some(increment) = text(node, "dimIncrement") {
+                        let increment = number(increment)?;
+                        if increment < block.size {
+                            return error(format!(
+                                "the elements of the cluster `{svd_name}` overlap"
+                            ));
+                        }
+                        if increment % block.align != 0 {
+                            return error(format!(
+                                "the elements of the cluster `{svd_name}` are not aligned"
+                            ));
+                        }
+                        block.size = increment;
+                    }
+                    let ty = block.name.clone();
+                    let (size, align) = (block.size, block.align);
+                    // Clusters sharing a `headerStructName` share the structure
+                    match blocks.iter().find(|b| b.name == block.name) {
+                        None => blocks.push(block),
+                        Some(b) if b.fields == block.fields && b.size == block.size => (),
+                        Some(_) => {
+                            return error(format!(
+                                "the clusters named `{ty}` have different registers"
+                            ));
+                        }
+                    }
+                    (ty, std::vec!["Inner"], size, align)
+                }
+                _ => continue,
+            };
+            let field = Field {
+                name: field_name(svd_name),
+                description: description_of(node),
+                offset,
+                ty,
+                access,
+                element_size,
+                align,
+                len: None,
+            };
+            let Some(dim) = text(node, "dim") else {
+                fields.push(field);
+                continue;
+            };
+            let dim = number(dim)?;
+            let increment = number(text(node, "dimIncrement").unwrap_or("0"))?;
+            if let Some(name) = svd_name.strip_suffix("[%s]") {
+                if increment != element_size {
+                    return error(format!(
+                        "the elements of the array `{svd_name}` are not adjacent"
+                    ));
+                }
+                fields.push(Field {
+                    name: field_name(name),
+                    len: Some(dim),
+                    ..field
+                });
+            } else {
+                for (i, index) in dim_indices(text(node, "dimIndex"), dim)?.iter().enumerate() {
+                    fields.push(Field {
+                        name: field_name(&svd_name.replace("%s", index)),
+                        offset: offset + i as u64 * increment,
+                        description: field.description.clone(),
+                        ty: field.ty.clone(),
+                        access: field.access.clone(),
+                        ..field
+                    });
+                }
+            }
+        }
+
+        for (i, field) in fields.iter().enumerate() {
+            if fields[..i].iter().any(|f| f.name == field.name) {
+                return error(format!(
+                    "more than one register of `{name}` is named `{}`",
+                    field.name
+                ));
+            }
+        }
+
+        fields.sort_by_key(|field| field.offset);
+        let mut size = 0;
+        let mut align = 1;
+        for field in &fields {
+            if field.offset < size {
+                return error(format!(
+                    "the register `{}` overlaps the previous register",
+                    field.name
+                ));
+            }
+            // A misaligned field would add padding to the structure
+            if field.offset % field.align != 0 {
+                return error(format!("the register `{}` is not aligned", field.name));
+            }
+            size = field.offset + field.size();
+            align = align.max(field.align);
+        }
+        Ok(Self {
+            name,
+            description,
+            fields,
+            size: size.next_multiple_of(align),
+            align,
+        })
+    }
+
+    fn write(&self, out: &mut String) -> fmt::Result {
+        write_doc(out, "", self.description.as_deref())?;
+        writeln!(out, "#[derive(derive_mmio::Mmio)]")?;
+        writeln!(out, "#[repr(C)]")?;
+        writeln!(out, "pub struct {} {{", self.name)?;
+        let mut end = 0;
+        let mut gaps = 0;
+        for field in &self.fields {
+            if field.offset > end {
+                writeln!(out, "    _reserved{gaps}: [u8; {:#x}],", field.offset - end)?;
+                gaps += 1;
+            }
+            write_doc(out, "    ", field.description.as_deref())?;
+            write!(out, "    #[mmio(")?;
+            for access in &field.access {
+                write!(out, "{access}, ")?;
+            }
+            writeln!(out, "offset = {:#x})]", field.offset)?;
+            match field.len {
+                Some(len) => writeln!(out, "    {}: [{}; {len}],", field.name, field.ty)?,
+                None => writeln!(out, "    {}: {},", field.name, field.ty)?,
+            }
+            end = field.offset + field.size();
+        }
+        if self.size > end {
+            writeln!(out, "    _reserved{gaps}: [u8; {:#x}],", self.size - end)?;
+        }
+        writeln!(out, "}}")
+    }
+}
+
+/// The `#[mmio(...)]` access attributes of a register.
+fn access(node: Node, properties: Properties) -> Result<Vec<&'static str>, ImportError> {
+    let (readable, writable) = match properties.access.unwrap_or("read-write") {
+        "read-only" => (true, false),
+        "write-only" | "writeOnce" => (false, true),
+        "read-write" | "read-writeOnce" => (true, true),
+        other => return error(format!("invalid access type `{other}`")),
+    };
+    let read = match (readable, text(node, "readAction")) {
+        (false, _) => None,
+        (true, None) => Some("PureRead"),
+        (true, Some("clear")) => Some("ClearOnRead"),
+        (true, Some(_)) => Some("Read"),
+    };
+    let write = match (writable, text(node, "modifiedWriteValues")) {
+        (false, _) => None,
+        (true, Some("oneToClear")) => Some("W1C"),
+        (true, Some("oneToSet")) => Some("W1S"),
+        (true, _) => Some("Write"),
+    };
+    Ok(match (read, write) {
+        // The default for read-write registers
+        (Some("PureRead"), Some("Write")) => Vec::new(),
+        (read, write) => read.into_iter().chain(write).collect(),
+    })
+}
+
+/// The names of the elements of an array which is expanded into separate fields.
+fn dim_indices(dim_index: Option<&str>, dim: u64) -> Result<Vec<String>, ImportError> {
+    let Some(dim_index) = dim_index else {
+        return Ok((0..dim).map(|i| i.to_string()).collect());
+    };
+    let indices: Vec<String> = match dim_index.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (number(start)?, number(end)?);
+            (start..=end).map(|i| i.to_string()).collect()
+        }
+        None => dim_index.split(',').map(|i| i.trim().to_owned()).collect(),
+    };
+    if indices.len() as u64 != dim {
+        return error(format!("`{dim_index}` does not have {dim} elements"));
+    }
+    Ok(indices)
+}
+
+fn write_doc(out: &mut String, indent: &str, description: Option<&str>) -> fmt::Result {
+    if let Some(description) = description {
+        writeln!(out, "{indent}/// {description}")?;
+    }
+    Ok(())
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+fn text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name)
+        .and_then(|child| child.text())
+        .map(str::trim)
+}
+
+/// The description of an element, on a single line.
+fn description_of(node: Node) -> Option<String> {
+    let description = text(node, "description")?;
+    let words: Vec<&str> = description.split_whitespace().collect();
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+/// Parse a `scaledNonNegativeInteger`.
+fn number(text: &str) -> Result<u64, ImportError> {
+    let text = text.trim();
+    let (digits, radix) =
+        if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            (hex, 16)
+        } else if let Some(binary) = text.strip_prefix('#') {
+            (binary, 2)
+        } else {
+            (text, 10)
+        };
+    let (digits, scale) = match digits.as_bytes().last() {
+        Some(b'k' | b'K') => (&digits[..digits.len() - 1], 1 << 10),
+        Some(b'm' | b'M') => (&digits[..digits.len() - 1], 1 << 20),
+        Some(b'g' | b'G') => (&digits[..digits.len() - 1], 1 << 30),
+        _ => (digits, 1),
+    };
+    u64::from_str_radix(digits, radix)
+        .ok()
+        .and_then(|value| value.checked_mul(scale))
+        .map_or_else(|| error(format!("invalid number `{text}`")), Ok)
+}
+
+/// Convert an SVD name like `UART_CTRL` to a type name like `UartCtrl`.
+fn type_name(name: &str) -> String {
+    let name = name.replace("%s", "");
+    let mut type_name = String::new();
+    for word in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            type_name.push(first.to_ascii_uppercase());
+            type_name.extend(chars.map(|c| c.to_ascii_lowercase()));
+        }
+    }
+    if !type_name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        type_name.insert(0, 'R');
+    }
+    type_name
+}
+
+/// Convert an SVD name like `CTRL%s` to a field name like `ctrl`.
+fn field_name(name: &str) -> String {
+    let name = name.replace("%s", "");
+    let mut field_name: String = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_ascii_lowercase();
+    // Fields starting with an underscore are reserved
+    if !field_name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        field_name.insert(0, 'r');
+    }
+    match field_name.as_str() {
+        // These can't be raw identifiers
+        "crate" | "self" | "super" => field_name.push('_'),
+        name if KEYWORDS.contains(&name) => field_name.insert_str(0, "r#"),
+        _ => (),
+    }
+    field_name
+}
+
+const KEYWORDS: [&str; 49] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "union", "unsafe", "unsized",
+    "use", "virtual", "where", "while", "yield",
+];
@@ -0,0 +1,472 @@
+//! Host-side helpers for testing drivers which use MMIO handles.
+//!
+//! This module is only available with the `testing` feature enabled.
+//!
+//! A [`RecordingPeripheral`] holds an in-memory instance of a register block
+//! and hands out MMIO handles which use the [`RecordingBackend`]. Every access
+//! performed through those handles is logged, so a test can check the exact
+//! sequence of register accesses a driver performs, and not just the final
+//! register contents.
+//!
+//! ```rust
+//! use derive_mmio::testing::{Access, RecordingPeripheral};
+//!
+//! #[derive(derive_mmio::Mmio)]
+//! #[repr(C)]
+//! struct Uart {
+//!     data: u32,
+//!     control: u32,
+//! }
+//!
+//! let peripheral = RecordingPeripheral::new(Uart { data: 0, control: 0 });
+//! let mut uart = peripheral.mmio();
+//! uart.write_control(1);
+//! uart.write_data(0x42);
+//!
+//! assert_eq!(
+//!     peripheral.accesses(),
+//!     [
+//!         Access::write("control", 0x4, 32, 1),
+//!         Access::write("data", 0x0, 32, 0x42),
+//!     ]
+//! );
+//! ```
+//!
+//! Hooks installed with [`RecordingPeripheral::on_read`] and
+//! [`RecordingPeripheral::on_write`] turn the peripheral into a simulation,
+//! for example of a status register which signals readiness after some polls,
+//! or of a data register which pops from a queue on every read.
+//!
+//! ```rust
+//! # #[derive(derive_mmio::Mmio)]
+//! # #[repr(C)]
+//! # struct Uart {
+//! #     data: u32,
+//! #     control: u32,
+//! # }
+//! use derive_mmio::testing::RecordingPeripheral;
+//!
+//! let peripheral = RecordingPeripheral::new(Uart { data: 0, control: 0 });
+//! let mut rx_queue = vec![0x41, 0x42];
+//! peripheral.on_read("data", move |_stored| rx_queue.remove(0));
+//!
+//! let mut uart = peripheral.mmio();
+//! assert_eq!(uart.read_data(), 0x41);
+//! assert_eq!(uart.read_data(), 0x42);
+//! ```
+
+use std::{
+    boxed::Box,
+    string::String,
+    sync::{Arc, Mutex},
+    vec::Vec,
+};
+
+use crate::{Backend, RegisterBlock, RegisterInfo};
+
+/// Whether a register was read or written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessKind {
+    /// The register was read.
+    Read,
+    /// The register was written.
+    Write,
+}
+
+/// A single register access logged by a [`RecordingPeripheral`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Access {
+    /// The name of the register, like `control`. Registers within inner blocks
+    /// are named `outer.inner` and array elements are named `array[index]`.
+    pub register: String,
+    /// The byte offset of the register from the start of the peripheral.
+    pub offset: usize,
+    /// The width of the access in bits.
+    pub width: u8,
+    /// Whether the register was read or written.
+    pub kind: AccessKind,
+    /// The value which was read or written.
+    pub value: u64,
+}
+
+impl Access {
+    /// Create a record of a read access, for comparing against the log.
+    pub fn read(register: &str, offset: usize, width: u8, value: u64) -> Self {
+        Self {
+            register: register.into(),
+            offset,
+            width,
+            kind: AccessKind::Read,
+            value,
+        }
+    }
+
+    /// Create a record of a write access, for comparing against the log.
+    pub fn write(register: &str, offset: usize, width: u8, value: u64) -> Self {
+        Self {
+            register: register.into(),
+            offset,
+            width,
+            kind: AccessKind::Write,
+            value,
+        }
+    }
+}
+
+impl core::fmt::Display for Access {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let kind = match self.kind {
+            AccessKind::Read => "read",
+            AccessKind::Write => "write",
+        };
+        write!(
+            f,
+            "{} {} @ {:#x} ({}-bit): {:#x}",
+            kind, self.register, self.offset, self.width, self.value
+        )
+    }
+}
+
+/// A hook which is called instead of reading a register.
+///
+/// Hooks are shared, so that they can be called without holding the lock on the
+/// list of peripherals.
+type ReadHook = Arc<Mutex<dyn FnMut(&mut u64) -> u64 + Send>>;
+
+/// A hook which is called instead of writing a register.
+type WriteHook = Arc<Mutex<dyn FnMut(&mut u64, u64) + Send>>;
+
+/// A peripheral registered with the [`RecordingBackend`].
+struct Entry {
+    base: usize,
+    size: usize,
+    registers: &'static [RegisterInfo],
+    log: Vec<Access>,
+    read_hooks: Vec<(String, ReadHook)>,
+    write_hooks: Vec<(String, WriteHook)>,
+}
+
+impl Entry {
+    fn register_name(&self, offset: usize) -> String {
+        let mut register = String::new();
+        write_register_name(self.registers, offset, &mut register);
+        register
+    }
+
+    /// Check that the register block has a register with the given name
+    fn has_register(&self, register: &str) -> bool {
+        (0..self.size).any(|offset| self.register_name(offset) == register)
+    }
+}
+
+/// Write the name of the register at the given byte offset into `name`.
+///
+/// Registers of inner blocks are written as `outer.inner` and array elements as
+/// `array[index]`. Nothing is written if there is no register at the offset.
+fn write_register_name(registers: &[RegisterInfo], offset: usize, name: &mut String) {
+    let Some(register) = registers.iter().find(|register| {
+        (register.offset..register.offset + register.total_size()).contains(&offset)
+    }) else {
+        return;
+    };
+    let offset = offset - register.offset;
+    name.push_str(register.name);
+    if register.len.is_some() {
+        name.push_str(&std::format!("[{}]", offset / register.size));
+    }
+    if let Some(inner) = register.inner {
+        name.push('.');
+        write_register_name(inner.registers, offset % register.size, name);
+    }
+}
+
+/// All currently live recording peripherals.
+static PERIPHERALS: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+fn with_peripherals<R>(f: impl FnOnce(&mut Vec<Entry>) -> R) -> R {
+    let mut peripherals = PERIPHERALS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    f(&mut peripherals)
+}
+
+/// A fixed-width register value which the [`RecordingBackend`] can access.
+trait Word: Copy {
+    const BITS: u8;
+
+    fn to_u64(self) -> u64;
+
+    fn from_u64(value: u64) -> Self;
+}
+
+macro_rules! impl_word {
+    ($($ty:ty),*) => {
+        $(
+            impl Word for $ty {
+                const BITS: u8 = <$ty>::BITS as u8;
+
+                fn to_u64(self) -> u64 {
+                    self.into()
+                }
+
+                fn from_u64(value: u64) -> Self {
+                    value as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(u8, u16, u32, u64);
+
+/// Perform an access for the [`RecordingBackend`], running any hook installed
+/// for the register, and log it against the peripheral which contains the
+/// address.
+///
+/// For writes, `value` is the value being written. The value read or written is
+/// returned.
+///
+/// Hooks run without holding the lock on the list of peripherals, so they can
+/// access registers themselves. Such accesses are logged before the access
+/// which ran the hook.
+///
+/// # Safety
+///
+/// If a peripheral contains the address, it must be valid for reads and writes.
+unsafe fn access<W: Word>(ptr: *mut W, kind: AccessKind, value: W) -> W {
+    let addr = ptr.addr();
+    let (base, register, read_hook, write_hook) = with_peripherals(|peripherals| {
+        let entry = peripherals
+            .iter()
+            .find(|entry| addr >= entry.base && addr < entry.base + entry.size)?;
+        let register = entry.register_name(addr - entry.base);
+        let read_hook = find_hook(&entry.read_hooks, &register);
+        let write_hook = find_hook(&entry.write_hooks, &register);
+        Some((entry.base, register, read_hook, write_hook))
+    })
+    .unwrap_or_else(|| panic!("no recording peripheral contains address {:#x}", addr));
+    let mut stored = unsafe { ptr.read_volatile() }.to_u64();
+    let value = match kind {
+        AccessKind::Read => match read_hook {
+            Some(hook) => lock(&hook)(&mut stored),
+            None => stored,
+        },
+        AccessKind::Write => {
+            let value = value.to_u64();
+            match write_hook {
+                Some(hook) => lock(&hook)(&mut stored, value),
+                None => stored = value,
+            }
+            value
+        }
+    };
+    unsafe { ptr.write_volatile(W::from_u64(stored)) };
+    with_peripherals(|peripherals| {
+        // The peripheral may have been dropped by a hook
+        if let Some(entry) = peripherals.iter_mut().find(|entry| entry.base == base) {
+            entry.log.push(Access {
+                register,
+                offset: addr - base,
+                width: W::BITS,
+                kind,
+                value,
+            });
+        }
+    });
+    W::from_u64(value)
+}
+
+/// Get a handle to the hook installed for the given register, if there is one.
+fn find_hook<H: ?Sized>(
+    hooks: &[(String, Arc<Mutex<H>>)],
+    register: &str,
+) -> Option<Arc<Mutex<H>>> {
+    hooks
+        .iter()
+        .find(|(name, _)| name == register)
+        .map(|(_, hook)| Arc::clone(hook))
+}
+
+/// Lock a hook, ignoring a panic in a previous call of the hook.
+fn lock<H: ?Sized>(hook: &Mutex<H>) -> std::sync::MutexGuard<'_, H> {
+    hook.lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// A [`Backend`] which logs every access in the [`RecordingPeripheral`] it
+/// belongs to.
+///
+/// MMIO handles using this backend are created with
+/// [`RecordingPeripheral::mmio`]. Accessing a register after the peripheral was
+/// dropped panics.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordingBackend;
+
+impl Backend for RecordingBackend {
+    unsafe fn read_u8(ptr: *const u8) -> u8 {
+        unsafe { access(ptr.cast_mut(), AccessKind::Read, 0) }
+    }
+
+    unsafe fn read_u16(ptr: *const u16) -> u16 {
+        unsafe { access(ptr.cast_mut(), AccessKind::Read, 0) }
+    }
+
+    unsafe fn read_u32(ptr: *const u32) -> u32 {
+        unsafe { access(ptr.cast_mut(), AccessKind::Read, 0) }
+    }
+
+    unsafe fn read_u64(ptr: *const u64) -> u64 {
+        unsafe { access(ptr.cast_mut(), AccessKind::Read, 0) }
+    }
+
+    unsafe fn write_u8(ptr: *mut u8, value: u8) {
+        unsafe { access(ptr, AccessKind::Write, value) };
+    }
+
+    unsafe fn write_u16(ptr: *mut u16, value: u16) {
+        unsafe { access(ptr, AccessKind::Write, value) };
+    }
+
+    unsafe fn write_u32(ptr: *mut u32, value: u32) {
+        unsafe { access(ptr, AccessKind::Write, value) };
+    }
+
+    unsafe fn write_u64(ptr: *mut u64, value: u64) {
+        unsafe { access(ptr, AccessKind::Write, value) };
+    }
+}
+
+/// An in-memory register block which logs every access made through its MMIO
+/// handles.
+///
+/// The register block is freed when the peripheral is dropped. MMIO handles
+/// created by [`RecordingPeripheral::mmio`] borrow the peripheral, so they can't
+/// outlive it.
+pub struct RecordingPeripheral<T: RegisterBlock> {
+    ptr: *mut T,
+}
+
+impl<T: RegisterBlock + 'static> RecordingPeripheral<T> {
+    /// Create a new recording peripheral with the given initial register values.
+    pub fn new(initial: T) -> Self {
+        let ptr = Box::into_raw(Box::new(initial));
+        with_peripherals(|peripherals| {
+            peripherals.push(Entry {
+                base: ptr.addr(),
+                size: core::mem::size_of::<T>(),
+                registers: T::REGISTERS,
+                log: Vec::new(),
+                read_hooks: Vec::new(),
+                write_hooks: Vec::new(),
+            })
+        });
+        Self { ptr }
+    }
+
+    /// Create a new MMIO handle for this peripheral.
+    ///
+    /// All accesses through the handle, and through any inner block handles
+    /// obtained from it, are logged.
+    pub fn mmio(&self) -> T::Mmio<'_, RecordingBackend> {
+        // Safety: The pointer points to an instance of `T` which is valid for as long as `self`
+        unsafe { T::new_mmio_with_backend(self.ptr) }
+    }
+
+    /// Get a copy of all accesses logged so far, in the order they happened.
+    pub fn accesses(&self) -> Vec<Access> {
+        self.with_entry(|entry| entry.log.clone())
+    }
+
+    /// Get all accesses logged so far and clear the log.
+    pub fn take_accesses(&self) -> Vec<Access> {
+        self.with_entry(|entry| core::mem::take(&mut entry.log))
+    }
+
+    /// Clear the log.
+    pub fn clear(&self) {
+        self.with_entry(|entry| entry.log.clear())
+    }
+
+    /// Install a hook which is called whenever the given register is read.
+    ///
+    /// The hook gets the value currently stored in the register, which it may
+    /// change, and returns the value seen by the driver. Registers are named
+    /// like in the access log, e.g. `data`, `bank.status` or `array[2]`.
+    /// Installing a second hook for the same register replaces the first one.
+    ///
+    /// The hook may access registers of this or any other recording peripheral,
+    /// except for the register it is installed for, which would deadlock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the register block has no register with the given name.
+    pub fn on_read(&self, register: &str, hook: impl FnMut(&mut u64) -> u64 + Send + 'static) {
+        self.with_entry(|entry| {
+            assert!(
+                entry.has_register(register),
+                "no register named `{}`",
+                register
+            );
+            entry.read_hooks.retain(|(name, _)| name != register);
+            entry
+                .read_hooks
+                .push((register.into(), Arc::new(Mutex::new(hook))));
+        })
+    }
+
+    /// Install a hook which is called whenever the given register is written.
+    ///
+    /// The hook gets the value currently stored in the register and the value
+    /// written by the driver. The written value is only stored if the hook
+    /// does so. Registers are named like in the access log, e.g. `data`,
+    /// `bank.status` or `array[2]`. Installing a second hook for the same
+    /// register replaces the first one.
+    ///
+    /// The hook may access registers of this or any other recording peripheral,
+    /// except for the register it is installed for, which would deadlock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the register block has no register with the given name.
+    pub fn on_write(&self, register: &str, hook: impl FnMut(&mut u64, u64) + Send + 'static) {
+        self.with_entry(|entry| {
+            assert!(
+                entry.has_register(register),
+                "no register named `{}`",
+                register
+            );
+            entry.write_hooks.retain(|(name, _)| name != register);
+            entry
+                .write_hooks
+                .push((register.into(), Arc::new(Mutex::new(hook))));
+        })
+    }
+
+    fn with_entry<R>(&self, f: impl FnOnce(&mut Entry) -> R) -> R {
+        with_peripherals(|peripherals| {
+            let entry = peripherals
+                .iter_mut()
+                .find(|entry| entry.base == self.ptr.addr())
+                .expect("recording peripheral is registered");
+            f(entry)
+        })
+    }
+}
+
+impl<T: RegisterBlock> Drop for RecordingPeripheral<T> {
+    fn drop(&mut self) {
+        let base = self.ptr.addr();
+        with_peripherals(|peripherals| peripherals.retain(|entry| entry.base != base));
+        // Safety: The pointer was created by `Box::into_raw` in `new`, and no MMIO
+        // handle borrowing `self` is left
+        drop(unsafe { Box::from_raw(self.ptr) });
+    }
+}
+
+impl<T: RegisterBlock> core::fmt::Debug for RecordingPeripheral<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("RecordingPeripheral")
+            .field(&self.ptr)
+            .finish()
+    }
+}
@@ -0,0 +1,81 @@
+use derive_mmio::InvalidAddress;
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Timer {
+    count: u32,
+    reload: u32,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[mmio(
+    address_ranges(0x4000_0000..0x4000_1000, RANGE_START..RANGE_END),
+    instances(UART0 = 0x4000_0000, UART1 = RANGE_START)
+)]
+#[repr(C)]
+struct Uart {
+    data: u64,
+    status: u32,
+    control: u32,
+}
+
+const RANGE_START: usize = 0x5000_0000;
+const RANGE_END: usize = 0x5000_0010;
+
+fn main() {
+    // Without ranges, the null pointer, the alignment and the end of the address
+    // space are checked.
+    assert_eq!(
+        unsafe { Timer::try_new_mmio_at(0) }.unwrap_err(),
+        InvalidAddress::Null
+    );
+    assert_eq!(
+        unsafe { Timer::try_new_mmio_at(0x1001) }.unwrap_err(),
+        InvalidAddress::Misaligned {
+            addr: 0x1001,
+            align: 4
+        }
+    );
+    let timer = unsafe { Timer::try_new_mmio_at(0x1004) }.unwrap();
+    assert_eq!(unsafe { timer.ptr() }.addr(), 0x1004);
+    assert_eq!(
+        unsafe { Timer::try_new_mmio_at(usize::MAX - 3) }.unwrap_err(),
+        InvalidAddress::Overflow(usize::MAX - 3)
+    );
+    assert!(unsafe { Timer::try_new_mmio_at(usize::MAX - 15) }.is_ok());
+
+    // The whole block must be within one of the ranges.
+    let align = core::mem::align_of::<Uart>();
+    assert_eq!(
+        unsafe { Uart::try_new_mmio_at(0x4000_0000 + align / 2) }.unwrap_err(),
+        InvalidAddress::Misaligned {
+            addr: 0x4000_0000 + align / 2,
+            align
+        }
+    );
+    assert!(unsafe { Uart::try_new_mmio_at(0x4000_0000) }.is_ok());
+    assert!(unsafe { Uart::try_new_mmio_at(0x4000_0FF0) }.is_ok());
+    assert_eq!(
+        unsafe { Uart::try_new_mmio_at(0x4000_0FF8) }.unwrap_err(),
+        InvalidAddress::OutOfRange(0x4000_0FF8)
+    );
+    assert!(unsafe { Uart::try_new_mmio_at(RANGE_START) }.is_ok());
+    assert_eq!(
+        unsafe { Uart::try_new_mmio_at(RANGE_END) }.unwrap_err(),
+        InvalidAddress::OutOfRange(RANGE_END)
+    );
+    assert_eq!(
+        unsafe { Uart::try_new_mmio_at(usize::MAX - 7) }.unwrap_err(),
+        InvalidAddress::Overflow(usize::MAX - 7)
+    );
+    assert_eq!(
+        InvalidAddress::OutOfRange(0x1000).to_string(),
+        "the address 0x1000 is outside of the allowed ranges"
+    );
+
+    // The instances are checked at compile time.
+    assert_eq!(
+        unsafe { UART1::new() }.pointer_to_status().addr(),
+        0x5000_0008
+    );
+}
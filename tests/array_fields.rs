@@ -1,4 +1,4 @@
-use derive_mmio::OutOfBoundsError;
+use derive_mmio::{OutOfBoundsError, OutOfRangeError};
 
 #[derive(derive_mmio::Mmio)]
 #[repr(C)]
@@ -97,10 +97,52 @@ fn main() {
     mmio_uart.write_array_write_only(0, 0xFF).unwrap();
     mmio_uart.write_array_write_only(1, 0xFF).unwrap();
 
+    // Indices given as const generics are checked at compile time.
+    mmio_uart.write_array_1_at::<1>(0x55);
+    mmio_uart.modify_array_1_at::<1>(|value| value + 1);
+    assert_eq!(mmio_uart.read_array_1_at::<1>(), 0x56);
+
+    // Range accesses are checked once for the whole range.
+    let first = mmio_uart.read_array_0(0).unwrap();
+    let last = mmio_uart.read_array_0(3).unwrap();
+    mmio_uart.write_array_0_from(1, &[0x20, 0x30]).unwrap();
+    let mut buf = [0; 4];
+    mmio_uart.read_array_0_into(0, &mut buf).unwrap();
+    assert_eq!(buf, [first, 0x20, 0x30, last]);
+    mmio_uart.write_array_0_from(4, &[]).unwrap();
+    let error = mmio_uart.write_array_0_from(3, &[0x40, 0x50]).unwrap_err();
+    assert_eq!(
+        error,
+        OutOfRangeError {
+            start: 3,
+            len: 2,
+            array_len: 4
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "out of bounds access at indices 3..5 of an array with 4 elements"
+    );
+    assert!(mmio_uart.read_array_0_into(5, &mut []).is_err());
+    assert!(mmio_uart.read_array_0_into(1, &mut buf).is_err());
+    assert_eq!(mmio_uart.read_array_0(3).unwrap(), last);
+
+    assert!(mmio_uart.write_array_0_from(5, &[]).is_err());
+    assert_eq!(mmio_uart.read_array_0(3).unwrap(), last);
+
     let mmio_uart = unsafe { Uart::new_mmio(core::ptr::addr_of_mut!(uart)) };
     // We can only use this to read the pure read-only array.
     assert_eq!(mmio_uart.read_array_read_only(0).unwrap(), 0x4);
     assert_eq!(mmio_uart.read_array_read_only(1).unwrap(), 0x3);
     assert_eq!(mmio_uart.read_array_read_only(2).unwrap(), 0x2);
     assert_eq!(mmio_uart.read_array_read_only(3).unwrap(), 0x1);
+
+    // Or use an index which is checked at compile time.
+    assert_eq!(mmio_uart.read_array_read_only_at::<3>(), 0x1);
+
+    // Or iterate over it.
+    let values: Vec<u32> = mmio_uart.iter_array_read_only().collect();
+    assert_eq!(values, [0x4, 0x3, 0x2, 0x1]);
+    assert_eq!(mmio_uart.iter_array_read_only().len(), 4);
+    assert_eq!(mmio_uart.iter_array_read_only().next_back(), Some(0x1));
 }
@@ -0,0 +1,166 @@
+use derive_mmio::c_header::Header;
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Channel {
+    config: u16,
+    #[mmio(Write)]
+    trigger: u16,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct DmaBank {
+    #[mmio(Inner)]
+    channel: Channel,
+    #[mmio(PureRead)]
+    status: u32,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Timer {
+    #[mmio(PureRead)]
+    count: u32,
+    #[mmio(PureRead, W1C)]
+    status: u32,
+    #[mmio(ClearOnRead)]
+    errors: u8,
+    _reserved: [u8; 7],
+    compare: [u64; 2],
+    #[mmio(Inner)]
+    channels: [Channel; 2],
+    #[mmio(Inner)]
+    banks: [DmaBank; 2],
+    #[mmio(Write)]
+    reload: u32,
+    _reserved_end: [u32; 1],
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct DMAChannel {
+    control: u32,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Fifo<T: Copy> {
+    data: T,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Board {
+    #[mmio(Inner)]
+    dma: DMAChannel,
+    #[mmio(Inner)]
+    narrow: Fifo<u16>,
+    #[mmio(Inner)]
+    narrow_too: Fifo<u16>,
+    #[mmio(Inner)]
+    wide: Fifo<u32>,
+}
+
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+struct Id([u8; 3]);
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Odd {
+    #[mmio(PureRead)]
+    id: Id,
+    ids: [Id; 2],
+    int: u8,
+    _reserved: [u8; 2],
+    register: u32,
+    r#if: u32,
+}
+
+fn main() {
+    let header = Header::<Timer>::new("timer_regs_t").to_string();
+    assert_eq!(
+        header,
+        r#"#ifndef TIMER_REGS_T_H
+#define TIMER_REGS_T_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+typedef volatile struct channel_t {
+    uint16_t config;
+    uint16_t trigger;
+} channel_t;
+
+_Static_assert(offsetof(channel_t, config) == 0x0, "channel_t.config must be at offset 0x0");
+_Static_assert(offsetof(channel_t, trigger) == 0x2, "channel_t.trigger must be at offset 0x2");
+_Static_assert(sizeof(channel_t) == 0x4, "channel_t must have a size of 0x4");
+
+typedef volatile struct dma_bank_t {
+    channel_t channel;
+    const uint32_t status;
+} dma_bank_t;
+
+_Static_assert(offsetof(dma_bank_t, channel) == 0x0, "dma_bank_t.channel must be at offset 0x0");
+_Static_assert(offsetof(dma_bank_t, status) == 0x4, "dma_bank_t.status must be at offset 0x4");
+_Static_assert(sizeof(dma_bank_t) == 0x8, "dma_bank_t must have a size of 0x8");
+
+typedef volatile struct timer_regs_t {
+    const uint32_t count;
+    uint32_t status;
+    const uint8_t errors;
+    uint8_t _reserved0[0x7];
+    uint64_t compare[2];
+    channel_t channels[2];
+    dma_bank_t banks[2];
+    uint32_t reload;
+    uint8_t _reserved1[0x4];
+} timer_regs_t;
+
+_Static_assert(offsetof(timer_regs_t, count) == 0x0, "timer_regs_t.count must be at offset 0x0");
+_Static_assert(offsetof(timer_regs_t, status) == 0x4, "timer_regs_t.status must be at offset 0x4");
+_Static_assert(offsetof(timer_regs_t, errors) == 0x8, "timer_regs_t.errors must be at offset 0x8");
+_Static_assert(offsetof(timer_regs_t, compare) == 0x10, "timer_regs_t.compare must be at offset 0x10");
+_Static_assert(offsetof(timer_regs_t, channels) == 0x20, "timer_regs_t.channels must be at offset 0x20");
+_Static_assert(offsetof(timer_regs_t, banks) == 0x28, "timer_regs_t.banks must be at offset 0x28");
+_Static_assert(offsetof(timer_regs_t, reload) == 0x38, "timer_regs_t.reload must be at offset 0x38");
+_Static_assert(sizeof(timer_regs_t) == 0x40, "timer_regs_t must have a size of 0x40");
+
+#endif /* TIMER_REGS_T_H */
+"#
+    );
+
+    // Acronyms are one word, and the instantiations of a generic block get a
+    // typedef each
+    let header = Header::<Board>::new("board_t").to_string();
+    assert!(header.contains("typedef volatile struct dma_channel_t {\n"));
+    assert!(header.contains("typedef volatile struct fifo_0_t {\n    uint16_t data;\n}"));
+    assert!(header.contains("typedef volatile struct fifo_1_t {\n    uint32_t data;\n}"));
+    assert_eq!(header.matches("typedef volatile struct").count(), 4);
+    assert!(header.contains(
+        "    dma_channel_t dma;\n    fifo_0_t narrow;\n    fifo_0_t narrow_too;\n    fifo_1_t wide;\n"
+    ));
+
+    // Registers without a matching integer type are byte arrays, and fields named
+    // like C keywords get a suffix
+    let header = Header::<Odd>::new("odd_t").to_string();
+    assert!(header.contains(
+        "typedef volatile struct odd_t {
+    const uint8_t id[3];
+    uint8_t ids[2][3];
+    uint8_t int_;
+    uint8_t _reserved0[0x2];
+    uint32_t register_;
+    uint32_t if_;
+} odd_t;
+"
+    ));
+    assert!(header.contains(
+        "_Static_assert(offsetof(odd_t, ids) == 0x3, \"odd_t.ids must be at offset 0x3\");"
+    ));
+    assert!(header.contains(
+        "_Static_assert(offsetof(odd_t, int_) == 0x9, \"odd_t.int_ must be at offset 0x9\");"
+    ));
+    assert!(header.contains("_Static_assert(offsetof(odd_t, if_) == 0x10, "));
+}
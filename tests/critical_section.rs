@@ -0,0 +1,37 @@
+#![cfg(feature = "critical-section")]
+
+#[derive(derive_mmio::Mmio)]
+#[mmio(instances(CLOCKS = 0x4000_0000))]
+#[repr(C)]
+struct Clocks {
+    enable: u32,
+    #[mmio(PureRead)]
+    status: u32,
+    #[mmio(split64)]
+    counter: u64,
+    dividers: [u16; 4],
+}
+
+fn main() {
+    let mut clocks = Clocks {
+        enable: 0b01,
+        status: 0,
+        counter: 0xFFFF_FFFF,
+        dividers: [1, 2, 3, 4],
+    };
+    let mmio = unsafe { Clocks::new_mmio(&mut clocks) };
+
+    // Only a shared reference is needed.
+    let shared = &mmio;
+    shared.modify_enable_cs(|value| value | 0b10);
+    shared.modify_counter_cs(|value| value + 1);
+    shared.modify_dividers_cs(2, |value| value * 10).unwrap();
+    assert!(matches!(
+        shared.modify_dividers_cs(4, |value| value),
+        Err(derive_mmio::OutOfBoundsError(4))
+    ));
+    assert_eq!(mmio.read_enable(), 0b11);
+    assert_eq!(mmio.read_counter(), 0x1_0000_0000);
+    assert_eq!(mmio.read_dividers(2).unwrap(), 30);
+    assert_eq!(mmio.read_dividers(3).unwrap(), 4);
+}
@@ -0,0 +1,102 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct UartBank {
+    data: u32,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    data: u32,
+    #[mmio(PureRead)]
+    status: u8,
+    #[mmio(PureRead)]
+    flags: u8,
+    control: u16,
+    array: [u32; 2],
+    #[mmio(Inner)]
+    bank: UartBank,
+}
+
+static READS: AtomicUsize = AtomicUsize::new(0);
+static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+/// A backend which counts all accesses and otherwise performs plain reads and writes.
+struct CountingBackend;
+
+impl derive_mmio::Backend for CountingBackend {
+    unsafe fn read_u8(ptr: *const u8) -> u8 {
+        READS.fetch_add(1, Ordering::Relaxed);
+        unsafe { ptr.read() }
+    }
+
+    unsafe fn read_u16(ptr: *const u16) -> u16 {
+        READS.fetch_add(1, Ordering::Relaxed);
+        unsafe { ptr.read() }
+    }
+
+    unsafe fn read_u32(ptr: *const u32) -> u32 {
+        READS.fetch_add(1, Ordering::Relaxed);
+        unsafe { ptr.read() }
+    }
+
+    unsafe fn read_u64(ptr: *const u64) -> u64 {
+        READS.fetch_add(1, Ordering::Relaxed);
+        unsafe { ptr.read() }
+    }
+
+    unsafe fn write_u8(ptr: *mut u8, value: u8) {
+        WRITES.fetch_add(1, Ordering::Relaxed);
+        unsafe { ptr.write(value) }
+    }
+
+    unsafe fn write_u16(ptr: *mut u16, value: u16) {
+        WRITES.fetch_add(1, Ordering::Relaxed);
+        unsafe { ptr.write(value) }
+    }
+
+    unsafe fn write_u32(ptr: *mut u32, value: u32) {
+        WRITES.fetch_add(1, Ordering::Relaxed);
+        unsafe { ptr.write(value) }
+    }
+
+    unsafe fn write_u64(ptr: *mut u64, value: u64) {
+        WRITES.fetch_add(1, Ordering::Relaxed);
+        unsafe { ptr.write(value) }
+    }
+}
+
+fn main() {
+    let mut uart = Uart {
+        data: 0xA,
+        status: 0x1,
+        flags: 0x2,
+        control: 0xC,
+        array: [0x1, 0x2],
+        bank: UartBank { data: 0x3 },
+    };
+
+    // Safety: We're pointing at a real object
+    let mut mmio_uart =
+        unsafe { Uart::new_mmio_with_backend::<CountingBackend>(core::ptr::addr_of_mut!(uart)) };
+    assert_eq!(mmio_uart.read_data(), 0xA);
+    mmio_uart.write_data(0xB);
+    assert_eq!(mmio_uart.read_status(), 0x1);
+    assert_eq!(mmio_uart.read_flags(), 0x2);
+    mmio_uart.modify_control(|c| c | 0x100);
+    assert_eq!(mmio_uart.read_array(1).unwrap(), 0x2);
+    mmio_uart.write_array(0, 0x5).unwrap();
+    // Inner blocks use the backend of the outer block.
+    mmio_uart.bank().write_data(0x4);
+    assert_eq!(mmio_uart.bank_shared().read_data(), 0x4);
+
+    assert_eq!(READS.load(Ordering::Relaxed), 6);
+    assert_eq!(WRITES.load(Ordering::Relaxed), 4);
+
+    assert_eq!(uart.data, 0xB);
+    assert_eq!(uart.control, 0x10C);
+    assert_eq!(uart.array, [0x5, 0x2]);
+    assert_eq!(uart.bank.data, 0x4);
+}
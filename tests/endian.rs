@@ -0,0 +1,81 @@
+use derive_mmio::RegisterAccess;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+struct Mode(u16);
+
+#[derive(derive_mmio::Mmio)]
+#[mmio(endian = "big")]
+#[repr(C)]
+struct Uart {
+    data: u32,
+    channels: [u16; 2],
+    mode: Mode,
+    #[mmio(endian = "little")]
+    status: u16,
+    _reserved: u32,
+    #[mmio(split64(latch))]
+    counter: u64,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Spi {
+    #[mmio(endian = "big")]
+    data: u32,
+    control: u32,
+}
+
+fn main() {
+    let mut uart = Uart {
+        data: 0x1234_5678u32.to_be(),
+        channels: [0x1u16.to_be(), 0x2u16.to_be()],
+        mode: Mode(0x0102u16.to_be()),
+        status: 0x0304,
+        _reserved: 0,
+        counter: 0,
+    };
+
+    // Safety: We're pointing at a real object
+    let mut mmio_uart = unsafe { Uart::new_mmio(core::ptr::addr_of_mut!(uart)) };
+    assert_eq!(mmio_uart.read_data(), 0x1234_5678);
+    assert_eq!(mmio_uart.read_channels(1).unwrap(), 0x2);
+    assert_eq!(mmio_uart.read_mode(), Mode(0x0102));
+    assert_eq!(mmio_uart.read_status(), 0x0304);
+    mmio_uart.modify_data(|data| data + 1);
+    mmio_uart.write_channels(0, 0xABCD).unwrap();
+    mmio_uart.write_mode(Mode(0x0201));
+    mmio_uart.write_counter(0x1122_3344_5566_7788);
+    assert_eq!(mmio_uart.read_counter(), 0x1122_3344_5566_7788);
+    assert_eq!(mmio_uart.snapshot().value("data"), Some(0x1234_5679));
+    assert_eq!(
+        Uart::REGISTERS[0].access,
+        RegisterAccess::PURE_READ
+            .union(RegisterAccess::WRITE)
+            .union(RegisterAccess::MODIFY)
+            .union(RegisterAccess::BIG_ENDIAN)
+    );
+    assert!(!Uart::REGISTERS[3]
+        .access
+        .contains(RegisterAccess::BIG_ENDIAN));
+    assert_eq!(uart.data, 0x1234_5679u32.to_be());
+    assert_eq!(uart.channels[0], 0xABCDu16.to_be());
+    assert_eq!(uart.mode, Mode(0x0201u16.to_be()));
+    // The high half of the counter comes first in memory, both in big endian.
+    assert_eq!(
+        uart.counter.to_ne_bytes(),
+        [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88]
+    );
+
+    let mut spi = Spi {
+        data: 0xAABB_CCDDu32.to_be(),
+        control: 0x1,
+    };
+
+    // Safety: We're pointing at a real object
+    let mut mmio_spi = unsafe { Spi::new_mmio(core::ptr::addr_of_mut!(spi)) };
+    assert_eq!(mmio_spi.read_data(), 0xAABB_CCDD);
+    assert_eq!(mmio_spi.read_control(), 0x1);
+    mmio_spi.write_data(0x1);
+    assert_eq!(spi.data.to_ne_bytes(), [0x0, 0x0, 0x0, 0x1]);
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "testing")]
+
+use std::sync::{Arc, Mutex};
+
+use derive_mmio::{
+    testing::{Access, RecordingPeripheral},
+    RegisterAccess,
+};
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Spi {
+    #[mmio(Fifo)]
+    rx: u32,
+    #[mmio(Write)]
+    tx: u32,
+    #[mmio(ClearOnRead)]
+    errors: u32,
+    #[mmio(Fifo)]
+    channels: [u16; 2],
+}
+
+fn main() {
+    let peripheral = RecordingPeripheral::new(Spi {
+        rx: 0,
+        tx: 0,
+        errors: 0b101,
+        channels: [0; 2],
+    });
+
+    // Reading the data register pops from an RX queue.
+    let rx = Arc::new(Mutex::new(vec![0x1, 0x2, 0x3, 0x4]));
+    let rx_hook = rx.clone();
+    peripheral.on_read("rx", move |_| rx_hook.lock().unwrap().remove(0));
+    peripheral.on_read("errors", core::mem::take);
+    let mut samples = 0;
+    peripheral.on_read("channels[1]", move |_| {
+        samples += 1;
+        samples
+    });
+
+    let mut spi = peripheral.mmio();
+    assert_eq!(spi.pop_rx(), 0x1);
+    let mut buf = [0; 3];
+    assert_eq!(spi.drain_rx(&mut buf), 3);
+    assert_eq!(buf, [0x2, 0x3, 0x4]);
+    assert!(rx.lock().unwrap().is_empty());
+    // Every value was read from the same address.
+    assert!(peripheral
+        .take_accesses()
+        .iter()
+        .all(|access| access.register == "rx" && access.offset == 0));
+
+    // Burst writes also go to the same address.
+    spi.write_tx_from_slice(&[0x5, 0x6]);
+    assert_eq!(
+        peripheral.take_accesses(),
+        [
+            Access::write("tx", 0x4, 32, 0x5),
+            Access::write("tx", 0x4, 32, 0x6),
+        ]
+    );
+
+    assert_eq!(spi.read_errors(), 0b101);
+    assert_eq!(spi.read_errors(), 0);
+
+    assert_eq!(spi.pop_channels(1).unwrap(), 1);
+    let mut buf = [0; 2];
+    assert_eq!(spi.drain_channels(1, &mut buf), 2);
+    assert_eq!(buf, [2, 3]);
+    assert_eq!(spi.drain_channels(2, &mut buf), 0);
+    assert_eq!(buf, [2, 3]);
+
+    // Neither kind of register is part of a snapshot.
+    assert_eq!(format!("{:?}", spi.snapshot()), "Spi");
+    assert_eq!(Spi::REGISTERS[0].access, RegisterAccess::FIFO);
+    assert!(Spi::REGISTERS[2].access.is_readable());
+}
@@ -0,0 +1,101 @@
+use derive_mmio::RegisterBlock;
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Channel {
+    control: u32,
+    #[mmio(PureRead)]
+    status: u32,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Dma<const CHANNELS: usize> {
+    #[mmio(offset = 0x0)]
+    control: u32,
+    #[mmio(Inner, offset = 0x4)]
+    channels: [Channel; CHANNELS],
+    counters: [u32; CHANNELS],
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Bank<T: Copy> {
+    data: T,
+    #[mmio(PureRead)]
+    status: T,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Port<T>
+where
+    T: Copy,
+{
+    #[mmio(Inner)]
+    bank: Bank<T>,
+    #[mmio(Inner)]
+    banks: [Bank<T>; 2],
+}
+
+fn channel(status: u32) -> Channel {
+    Channel { control: 0, status }
+}
+
+/// Count the registers of any register block, including generic ones.
+fn register_count<T: RegisterBlock>() -> usize {
+    T::REGISTERS.len()
+}
+
+fn main() {
+    let mut dma4 = Dma::<4> {
+        control: 0,
+        channels: [channel(0), channel(1), channel(2), channel(3)],
+        counters: [0; 4],
+    };
+    let mut dma8 = Dma::<8> {
+        control: 0,
+        channels: core::array::from_fn(|i| channel(i as u32)),
+        counters: [0; 8],
+    };
+
+    // Safety: We're pointing at real objects
+    let mut mmio_dma4 = unsafe { Dma::new_mmio(core::ptr::addr_of_mut!(dma4)) };
+    let mut mmio_dma8: MmioDma<'_, 8> = unsafe { Dma::new_mmio(core::ptr::addr_of_mut!(dma8)) };
+    assert_eq!(mmio_dma4.len_channels(), 4);
+    assert_eq!(mmio_dma8.len_channels(), 8);
+    assert_eq!(mmio_dma4.channels(3).unwrap().read_status(), 3);
+    assert_eq!(mmio_dma8.channels_at::<7>().read_status(), 7);
+    assert!(mmio_dma4.channels(4).is_err());
+    mmio_dma8.write_counters(5, 0x55).unwrap();
+    mmio_dma8.modify_control(|control| control | 1);
+    assert_eq!(mmio_dma8.iter_counters().sum::<u32>(), 0x55);
+    assert_eq!(mmio_dma8.read_control(), 1);
+    assert_eq!(dma8.counters[5], 0x55);
+
+    assert_eq!(Dma::<4>::REGISTERS[1].len, Some(4));
+    assert_eq!(Dma::<8>::REGISTERS[2].offset, 0x4 + 8 * 8);
+    assert_eq!(register_count::<Dma<8>>(), 3);
+
+    let mut port = Port::<u16> {
+        bank: Bank {
+            data: 0x1,
+            status: 0x2,
+        },
+        banks: [Bank { data: 0, status: 0 }, Bank { data: 0, status: 0 }],
+    };
+
+    // Safety: We're pointing at a real object
+    let mut mmio_port = unsafe { Port::new_mmio(core::ptr::addr_of_mut!(port)) };
+    assert_eq!(mmio_port.bank().read_data(), 0x1);
+    assert_eq!(mmio_port.bank_shared().read_status(), 0x2);
+    mmio_port.banks(1).unwrap().write_data(0xABCD);
+    let bank: MmioBank<'_, u16> = mmio_port.banks_at::<1>();
+    assert_eq!(bank.read_data(), 0xABCD);
+    assert_eq!(
+        format!("{:?}", mmio_port.snapshot()),
+        "Port { bank: Bank { data: 0x0001, status: 0x0002 }, \
+         banks: [Bank { data: 0x0000, status: 0x0000 }, \
+         Bank { data: 0xabcd, status: 0x0000 }] }"
+    );
+}
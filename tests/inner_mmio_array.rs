@@ -54,6 +54,23 @@ fn main() {
     assert_eq!(bank0.read_data(), 0x42);
     assert_eq!(bank0.read_status(), 0x13);
 
+    // Indices given as const generics are checked at compile time.
+    mmio_uart.banks_at::<1>().write_data(0x7);
+    assert_eq!(mmio_uart.banks_shared_at::<1>().read_data(), 0x7);
+    mmio_uart.banks_at::<1>().write_data(0x2);
+
+    // Iterate over all banks.
+    let data: Vec<u32> = mmio_uart
+        .iter_banks()
+        .map(|bank| bank.read_data())
+        .collect();
+    assert_eq!(data, [0x42, 0x2]);
+    assert_eq!(mmio_uart.iter_banks().len(), 2);
+    assert_eq!(
+        mmio_uart.iter_banks().next_back().unwrap().read_status(),
+        0x3
+    );
+
     unsafe {
         let inner_owned_for_p0 = mmio_uart.steal_banks_shared(0).unwrap();
         let inner_owned_for_p1 = mmio_uart.steal_banks_shared(1).unwrap();
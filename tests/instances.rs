@@ -0,0 +1,104 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct UartBank {
+    data: u32,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[mmio(instances(UART0 = 0x4000_0000, UART1 = UART1_BASE))]
+#[repr(C)]
+struct Uart {
+    data: u32,
+    #[mmio(PureRead)]
+    status: u32,
+    array: [u32; 2],
+    #[mmio(Inner)]
+    bank: UartBank,
+}
+
+const UART1_BASE: usize = 0x4000_1000;
+
+/// Simulated memory for both UART instances.
+static MEMORY: [[AtomicU32; 5]; 2] = [const { [const { AtomicU32::new(0) }; 5] }; 2];
+
+/// A backend which maps the addresses of both UART instances to [`MEMORY`].
+struct MappedBackend;
+
+impl MappedBackend {
+    fn word(ptr: *const u32) -> &'static AtomicU32 {
+        let addr = ptr.addr();
+        let instance = (addr - 0x4000_0000) / 0x1000;
+        &MEMORY[instance][(addr % 0x1000) / 4]
+    }
+}
+
+impl derive_mmio::Backend for MappedBackend {
+    unsafe fn read_u8(_ptr: *const u8) -> u8 {
+        unreachable!("8-bit registers are not used by this test")
+    }
+
+    unsafe fn read_u16(_ptr: *const u16) -> u16 {
+        unreachable!("16-bit registers are not used by this test")
+    }
+
+    unsafe fn read_u32(ptr: *const u32) -> u32 {
+        Self::word(ptr).load(Ordering::Relaxed)
+    }
+
+    unsafe fn read_u64(_ptr: *const u64) -> u64 {
+        unreachable!("64-bit registers are not used by this test")
+    }
+
+    unsafe fn write_u8(_ptr: *mut u8, _value: u8) {
+        unreachable!("8-bit registers are not used by this test")
+    }
+
+    unsafe fn write_u16(_ptr: *mut u16, _value: u16) {
+        unreachable!("16-bit registers are not used by this test")
+    }
+
+    unsafe fn write_u32(ptr: *mut u32, value: u32) {
+        Self::word(ptr).store(value, Ordering::Relaxed)
+    }
+
+    unsafe fn write_u64(_ptr: *mut u64, _value: u64) {
+        unreachable!("64-bit registers are not used by this test")
+    }
+}
+
+/// Generic code which works with any handle to a UART.
+fn send(uart: &mut MmioUart<'_, MappedBackend>, value: u32) {
+    uart.write_data(value);
+}
+
+fn main() {
+    // The handles don't store an address.
+    assert_eq!(core::mem::size_of::<UART0>(), 0);
+    assert_eq!(core::mem::size_of::<MmioUartAt<0x4000_2000>>(), 0);
+
+    // Safety: The addresses are valid for the mapped backend
+    let mut uart0 = unsafe { MmioUartAt::<0x4000_0000, MappedBackend>::new() };
+    let mut uart1 = unsafe { MmioUartAt::<UART1_BASE, MappedBackend>::new() };
+    uart0.write_data(0x1);
+    uart1.write_data(0x2);
+    uart1.write_array(1, 0x3).unwrap();
+    uart1.bank().write_data(0x4);
+    send(&mut uart0.as_mmio(), 0x5);
+    assert_eq!(MEMORY[0][0].load(Ordering::Relaxed), 0x5);
+    assert_eq!(MEMORY[1][0].load(Ordering::Relaxed), 0x2);
+    assert_eq!(MEMORY[1][3].load(Ordering::Relaxed), 0x3);
+    assert_eq!(uart1.bank_shared().read_data(), 0x4);
+    assert_eq!(uart1.read_status(), 0x0);
+    assert_eq!(uart1.snapshot().value("bank.data"), Some(0x4));
+    assert_eq!(unsafe { uart1.ptr() }.addr(), 0x4000_1000);
+    assert_eq!(
+        unsafe { UART1::new() }.pointer_to_status().addr(),
+        0x4000_1004
+    );
+    assert_eq!(
+        format!("{:?}", unsafe { UART0::new() }),
+        "MmioUartAt(0x40000000)"
+    );
+}
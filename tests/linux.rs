@@ -0,0 +1,53 @@
+#![cfg(all(feature = "std", target_os = "linux"))]
+
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use derive_mmio::linux::Mapping;
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    data: u32,
+    #[mmio(PureRead)]
+    status: u32,
+}
+
+fn main() {
+    let mut file = tempfile::tempfile().unwrap();
+    file.set_len(0x2000).unwrap();
+    file.seek(SeekFrom::Start(0x1008)).unwrap();
+    file.write_all(&[0x11, 0, 0, 0, 0x22, 0, 0, 0]).unwrap();
+
+    // The offset does not need to be aligned to a page
+    let mut uart = unsafe { Mapping::<Uart>::new(&file, 0x1008) }.unwrap();
+    assert_eq!(uart.read_status(), 0x22);
+    assert_eq!(uart.as_ptr().addr() % 0x1000, 0x008);
+    uart.write_data(0x33);
+    assert_eq!(uart.read_data(), 0x33);
+    drop(uart);
+
+    let mut contents = [0; 8];
+    file.seek(SeekFrom::Start(0x1008)).unwrap();
+    file.read_exact(&mut contents).unwrap();
+    assert_eq!(contents, [0x33, 0, 0, 0, 0x22, 0, 0, 0]);
+
+    // The register block must be aligned
+    let error = unsafe { Mapping::<Uart>::new(&file, 0x1002) }.unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+
+    // A file opened by path
+    let mut named = tempfile::NamedTempFile::new().unwrap();
+    named.write_all(&[0; 4]).unwrap();
+    named.write_all(&0x44u32.to_ne_bytes()).unwrap();
+    let mut uart = unsafe { Mapping::<Uart>::open(named.path(), 0) }.unwrap();
+    assert_eq!(uart.read_status(), 0x44);
+    assert!(format!("{uart:?}").starts_with("Mapping(0x"));
+
+    // Swapped handles stay usable after the other mapping was dropped
+    let mut other = unsafe { Mapping::<Uart>::new(&file, 0x1008) }.unwrap();
+    core::mem::swap(&mut *uart, &mut *other);
+    drop(other);
+    assert_eq!(uart.read_data(), 0x33);
+    uart.write_data(0x55);
+    drop(uart);
+}
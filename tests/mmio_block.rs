@@ -0,0 +1,54 @@
+#[derive_mmio::mmio_block(size = 0x10)]
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct UartBank {
+    data: u32,
+    #[mmio(PureRead, offset = 0x8)]
+    status: u32,
+}
+
+#[derive_mmio::mmio_block]
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    data: u32,
+    #[mmio(offset = 0x04)]
+    control: u32,
+    #[mmio(Read, offset = 0x1C)]
+    status: u32,
+    array: [u32; 2],
+    #[mmio(Inner, offset = 0x30)]
+    bank: UartBank,
+    #[mmio(offset = UART_BANKS_OFFSET, Inner)]
+    banks: [UartBank; 2],
+    #[mmio(PureRead, offset = 0x80)]
+    id: u32,
+}
+
+const UART_BANKS_OFFSET: usize = 0x50;
+
+fn main() {
+    assert_eq!(core::mem::size_of::<UartBank>(), 0x10);
+    assert_eq!(core::mem::offset_of!(Uart, status), 0x1C);
+    assert_eq!(core::mem::offset_of!(Uart, array), 0x20);
+    assert_eq!(core::mem::offset_of!(Uart, bank), 0x30);
+    assert_eq!(core::mem::offset_of!(Uart, banks), 0x50);
+    assert_eq!(core::mem::size_of::<Uart>(), 0x84);
+
+    let mut memory = [0u32; 0x84 / 4];
+    memory[0x1C / 4] = 0xF;
+    memory[0x24 / 4] = 0x2;
+    memory[0x38 / 4] = 0x3;
+    memory[0x60 / 4] = 0x5;
+    memory[0x80 / 4] = 0x1234;
+
+    // Safety: We're pointing at a real object which is big enough and aligned
+    let mut mmio_uart = unsafe { Uart::new_mmio(memory.as_mut_ptr().cast()) };
+    assert_eq!(mmio_uart.read_status(), 0xF);
+    assert_eq!(mmio_uart.read_array(1).unwrap(), 0x2);
+    assert_eq!(mmio_uart.bank().read_status(), 0x3);
+    assert_eq!(mmio_uart.banks(1).unwrap().read_data(), 0x5);
+    assert_eq!(mmio_uart.read_id(), 0x1234);
+    mmio_uart.write_control(0xC);
+    assert_eq!(memory[1], 0xC);
+}
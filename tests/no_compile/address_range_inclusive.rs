@@ -0,0 +1,8 @@
+#[derive(derive_mmio::Mmio)]
+#[mmio(address_ranges(0x4000_0000..=0x4000_FFFF))]
+#[repr(C)]
+struct Uart {
+    data: u32,
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+// The default `Backend::read` and `Backend::write` only support registers with
+// a size of 1, 2, 4 or 8 bytes.
+struct Bus;
+
+impl derive_mmio::Backend for Bus {
+    unsafe fn read_u8(_ptr: *const u8) -> u8 {
+        0
+    }
+    unsafe fn read_u16(_ptr: *const u16) -> u16 {
+        0
+    }
+    unsafe fn read_u32(_ptr: *const u32) -> u32 {
+        0
+    }
+    unsafe fn read_u64(_ptr: *const u64) -> u64 {
+        0
+    }
+    unsafe fn write_u8(_ptr: *mut u8, _value: u8) {}
+    unsafe fn write_u16(_ptr: *mut u16, _value: u16) {}
+    unsafe fn write_u32(_ptr: *mut u32, _value: u32) {}
+    unsafe fn write_u64(_ptr: *mut u64, _value: u64) {}
+}
+
+fn main() {
+    let mut register = [0u8; 3];
+    let _ = unsafe { <Bus as derive_mmio::Backend>::read(&raw const register) };
+    unsafe { <Bus as derive_mmio::Backend>::write(&raw mut register, [1; 3]) };
+}
@@ -0,0 +1,8 @@
+#[derive(derive_mmio::Mmio)]
+#[mmio(bus_width = 24)]
+#[repr(C)]
+struct Timer {
+    control: u32,
+}
+
+fn main() {}
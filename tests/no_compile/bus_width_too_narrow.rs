@@ -0,0 +1,10 @@
+#[derive(derive_mmio::Mmio)]
+#[mmio(bus_width = 32)]
+#[repr(C)]
+struct Timer {
+    control: u32,
+    _reserved: u32,
+    counter: u64,
+}
+
+fn main() {}
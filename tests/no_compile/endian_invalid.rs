@@ -0,0 +1,8 @@
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    #[mmio(endian = "middle")]
+    data: u32,
+}
+
+fn main() {}
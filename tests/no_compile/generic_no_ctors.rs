@@ -0,0 +1,10 @@
+// The layout of a generic register block is checked by its constructors, so they
+// can't be omitted.
+#[derive(derive_mmio::Mmio)]
+#[mmio(no_ctors)]
+#[repr(C)]
+struct Port<const N: usize> {
+    data: [u32; N],
+}
+
+fn main() {}
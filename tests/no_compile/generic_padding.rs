@@ -0,0 +1,12 @@
+// The layout of a generic register block is also checked when its register
+// table is used.
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Port<T: Copy> {
+    flags: u8,
+    data: T,
+}
+
+fn main() {
+    let _ = Port::<u32>::REGISTERS;
+}
@@ -0,0 +1,7 @@
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart<B: Copy> {
+    data: B,
+}
+
+fn main() {}
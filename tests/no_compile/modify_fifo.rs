@@ -0,0 +1,9 @@
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    // Modify on a FIFO register.
+    #[mmio(Fifo, Write, Modify)]
+    data: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    // Modify on a write-one-to-clear register.
+    #[mmio(PureRead, Write, Modify, W1C)]
+    interrupts: u32,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    #[mmio(offset)]
+    data: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[derive_mmio::mmio_block(size = 0x10)]
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    data: [u32; 4],
+    #[mmio(offset = 0x8)]
+    control: u32,
+}
+
+fn main() {}
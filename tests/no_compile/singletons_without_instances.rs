@@ -0,0 +1,8 @@
+#[derive(derive_mmio::Mmio)]
+#[mmio(singletons)]
+#[repr(C)]
+struct Uart {
+    data: u32,
+}
+
+fn main() {}
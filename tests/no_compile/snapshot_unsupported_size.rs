@@ -0,0 +1,20 @@
+// Snapshots can only read registers which are 1, 2, 4 or 8 bytes in size.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+struct Id([u8; 3]);
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    id: Id,
+    _reserved: u8,
+}
+
+fn main() {
+    let mut uart = Uart {
+        id: Id([1, 2, 3]),
+        _reserved: 0,
+    };
+    let mmio_uart = unsafe { Uart::new_mmio(&mut uart) };
+    let _ = mmio_uart.snapshot();
+}
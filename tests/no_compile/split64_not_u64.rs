@@ -0,0 +1,8 @@
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Timer {
+    #[mmio(split64)]
+    counter: u32,
+}
+
+fn main() {}
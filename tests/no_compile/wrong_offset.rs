@@ -0,0 +1,11 @@
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    #[mmio(offset = 0x0)]
+    data: u32,
+    _reserved: [u32; 5],
+    #[mmio(offset = 0x1C)]
+    status: u32,
+}
+
+fn main() {}
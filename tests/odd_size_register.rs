@@ -0,0 +1,27 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+struct Id([u8; 3]);
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Device {
+    #[mmio(Read, Write)]
+    id: Id,
+    flags: u8,
+}
+
+fn main() {
+    let mut device = Device {
+        id: Id([0x1, 0x2, 0x3]),
+        flags: 0x4,
+    };
+
+    // Safety: We're pointing at a real object
+    let mut mmio_device = unsafe { Device::new_mmio(core::ptr::addr_of_mut!(device)) };
+    // Registers which are not 1, 2, 4 or 8 bytes in size are supported by the
+    // default backend.
+    assert_eq!(mmio_device.read_id(), Id([0x1, 0x2, 0x3]));
+    mmio_device.write_id(Id([0xA, 0xB, 0xC]));
+    assert_eq!(mmio_device.read_id(), Id([0xA, 0xB, 0xC]));
+    assert_eq!(mmio_device.read_flags(), 0x4);
+}
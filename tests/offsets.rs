@@ -0,0 +1,58 @@
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct UartBank {
+    #[mmio(offset = 0x0)]
+    data: u32,
+    #[mmio(PureRead, offset = 0x4)]
+    status: u32,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    #[mmio(offset = 0x00)]
+    data: u32,
+    #[mmio(offset = 0x04)]
+    control: u32,
+    _reserved: [u32; 5],
+    #[mmio(Read, offset = 0x1C)]
+    status: u32,
+    #[mmio(offset = 0x20)]
+    array: [u32; 2],
+    #[mmio(Inner, offset = 0x28)]
+    bank: UartBank,
+    #[mmio(offset = UART_BANKS_OFFSET, Inner)]
+    banks: [UartBank; 2],
+}
+
+const UART_BANKS_OFFSET: usize = 0x30;
+
+fn main() {
+    let mut uart = Uart {
+        data: 0xA,
+        control: 0xC,
+        _reserved: [0; 5],
+        status: 0xF,
+        array: [0x1, 0x2],
+        bank: UartBank {
+            data: 0x1,
+            status: 0x2,
+        },
+        banks: [
+            UartBank {
+                data: 0x3,
+                status: 0x4,
+            },
+            UartBank {
+                data: 0x5,
+                status: 0x6,
+            },
+        ],
+    };
+
+    // Safety: We're pointing at a real object
+    let mut mmio_uart = unsafe { Uart::new_mmio(core::ptr::addr_of_mut!(uart)) };
+    assert_eq!(mmio_uart.read_status(), 0xF);
+    assert_eq!(mmio_uart.bank().read_status(), 0x2);
+    assert_eq!(mmio_uart.banks(1).unwrap().read_data(), 0x5);
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "testing")]
+
+use derive_mmio::testing::{Access, RecordingPeripheral};
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct UartBank {
+    data: u32,
+    #[mmio(PureRead)]
+    status: u16,
+    #[mmio(PureRead)]
+    flags: u8,
+    _reserved: u8,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    data: u32,
+    control: u32,
+    array: [u32; 2],
+    #[mmio(Inner)]
+    bank: UartBank,
+    #[mmio(Inner)]
+    banks: [UartBank; 2],
+    #[mmio(Read)]
+    fifo: u32,
+}
+
+fn bank() -> UartBank {
+    UartBank {
+        data: 0x1,
+        status: 0x2,
+        flags: 0x3,
+        _reserved: 0,
+    }
+}
+
+fn main() {
+    let peripheral = RecordingPeripheral::new(Uart {
+        data: 0xA,
+        control: 0,
+        array: [0x1, 0x2],
+        bank: bank(),
+        banks: [bank(), bank()],
+        fifo: 0,
+    });
+    let mut uart = peripheral.mmio();
+
+    // Enable the peripheral before sending data.
+    uart.modify_control(|c| c | 1);
+    uart.write_data(0x42);
+    assert_eq!(
+        peripheral.take_accesses(),
+        [
+            Access::read("control", 0x4, 32, 0x0),
+            Access::write("control", 0x4, 32, 0x1),
+            Access::write("data", 0x0, 32, 0x42),
+        ]
+    );
+
+    assert_eq!(uart.read_array(1).unwrap(), 0x2);
+    assert_eq!(uart.bank().read_status(), 0x2);
+    assert_eq!(uart.banks(1).unwrap().read_flags(), 0x3);
+    uart.banks(0).unwrap().write_data(0x5);
+    assert_eq!(
+        peripheral.accesses(),
+        [
+            Access::read("array[1]", 0xC, 32, 0x2),
+            Access::read("bank.status", 0x14, 16, 0x2),
+            Access::read("banks[1].flags", 0x26, 8, 0x3),
+            Access::write("banks[0].data", 0x18, 32, 0x5),
+        ]
+    );
+    assert_eq!(
+        peripheral.accesses()[0].to_string(),
+        "read array[1] @ 0xc (32-bit): 0x2"
+    );
+
+    peripheral.clear();
+    assert!(peripheral.accesses().is_empty());
+
+    // Snapshots only perform reads without side effects.
+    let snapshot = uart.snapshot();
+    assert_eq!(snapshot.value("banks[1].status"), Some(0x2));
+    let accesses = peripheral.take_accesses();
+    assert_eq!(accesses.len(), 13);
+    assert!(accesses.iter().all(|access| access.register != "fifo"));
+    assert_eq!(accesses[12], Access::read("banks[1].flags", 0x26, 8, 0x3));
+
+    // Handles of other peripherals are logged separately.
+    let other = RecordingPeripheral::new(bank());
+    other.mmio().write_data(0x7);
+    assert!(peripheral.accesses().is_empty());
+    assert_eq!(other.accesses(), [Access::write("data", 0x0, 32, 0x7)]);
+}
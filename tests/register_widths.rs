@@ -0,0 +1,85 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+struct Mode(u32);
+
+#[derive(derive_mmio::Mmio)]
+#[mmio(bus_width = 32)]
+#[repr(C)]
+struct Uart {
+    data: u8,
+    #[mmio(PureRead)]
+    status: u8,
+    control: u16,
+    baud: u32,
+    fifo: [u8; 4],
+    channels: [u16; 2],
+    mode: Mode,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[mmio(bus_width = 64)]
+#[repr(C)]
+struct Timer {
+    counter: u64,
+    compare: [u64; 2],
+    reload: u32,
+    prescaler: u16,
+    control: u8,
+    _reserved: u8,
+}
+
+fn main() {
+    let mut uart = Uart {
+        data: 0x1,
+        status: 0x2,
+        control: 0x1234,
+        baud: 115200,
+        fifo: [0x1, 0x2, 0x3, 0x4],
+        channels: [0xAAAA, 0x5555],
+        mode: Mode(0x3),
+    };
+
+    // Safety: We're pointing at a real object
+    let mut mmio_uart = unsafe { Uart::new_mmio(core::ptr::addr_of_mut!(uart)) };
+    assert_eq!(mmio_uart.read_data(), 0x1);
+    assert_eq!(mmio_uart.read_status(), 0x2);
+    assert_eq!(mmio_uart.read_control(), 0x1234);
+    assert_eq!(mmio_uart.read_baud(), 115200);
+    assert_eq!(mmio_uart.read_fifo(3).unwrap(), 0x4);
+    assert_eq!(mmio_uart.read_channels(1).unwrap(), 0x5555);
+    assert_eq!(mmio_uart.read_mode(), Mode(0x3));
+    mmio_uart.write_data(0xFF);
+    mmio_uart.modify_control(|control| control | 0x8000);
+    mmio_uart.write_fifo(0, 0x10).unwrap();
+    mmio_uart.write_channels(0, 0xBEEF).unwrap();
+    assert_eq!(mmio_uart.read_data(), 0xFF);
+    assert_eq!(mmio_uart.read_control(), 0x9234);
+    assert_eq!(mmio_uart.read_fifo(0).unwrap(), 0x10);
+    assert_eq!(mmio_uart.read_channels(0).unwrap(), 0xBEEF);
+    // The neighbouring registers were not touched by the narrow writes.
+    assert_eq!(mmio_uart.read_status(), 0x2);
+    assert_eq!(mmio_uart.read_fifo(1).unwrap(), 0x2);
+    assert_eq!(mmio_uart.read_channels(1).unwrap(), 0x5555);
+
+    let mut timer = Timer {
+        counter: 0x1234_5678_9ABC_DEF0,
+        compare: [u64::MAX, 0],
+        reload: 0x1000,
+        prescaler: 0x10,
+        control: 0x1,
+        _reserved: 0,
+    };
+
+    // Safety: We're pointing at a real object
+    let mut mmio_timer = unsafe { Timer::new_mmio(core::ptr::addr_of_mut!(timer)) };
+    assert_eq!(mmio_timer.read_counter(), 0x1234_5678_9ABC_DEF0);
+    assert_eq!(mmio_timer.read_compare(0).unwrap(), u64::MAX);
+    mmio_timer.write_compare(1, 0xDEAD_BEEF_0000_0001).unwrap();
+    assert_eq!(mmio_timer.read_compare(1).unwrap(), 0xDEAD_BEEF_0000_0001);
+    assert_eq!(mmio_timer.read_reload(), 0x1000);
+    assert_eq!(mmio_timer.read_prescaler(), 0x10);
+    assert_eq!(mmio_timer.read_control(), 0x1);
+
+    assert_eq!(Timer::REGISTERS[0].size, 8);
+    assert_eq!(Timer::REGISTERS[3].size, 2);
+}
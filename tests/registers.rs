@@ -0,0 +1,96 @@
+use derive_mmio::{InnerBlockInfo, RegisterAccess, RegisterBlock, RegisterInfo};
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct UartBank {
+    data: u32,
+    #[mmio(PureRead)]
+    status: u32,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    data: u8,
+    _reserved: [u8; 3],
+    #[mmio(Read, Write)]
+    fifo: u32,
+    #[mmio(PureRead, Write)]
+    array: [u16; 4],
+    #[mmio(Inner)]
+    banks: [UartBank; 2],
+}
+
+fn main() {
+    let bank_registers = [
+        RegisterInfo {
+            name: "data",
+            offset: 0,
+            size: 4,
+            len: None,
+            access: RegisterAccess::PURE_READ
+                .union(RegisterAccess::WRITE)
+                .union(RegisterAccess::MODIFY),
+            inner: None,
+        },
+        RegisterInfo {
+            name: "status",
+            offset: 4,
+            size: 4,
+            len: None,
+            access: RegisterAccess::PURE_READ,
+            inner: None,
+        },
+    ];
+    assert_eq!(UartBank::REGISTERS, bank_registers);
+
+    assert_eq!(Uart::REGISTERS.len(), 4);
+    assert_eq!(Uart::REGISTERS[0].name, "data");
+    assert_eq!(Uart::REGISTERS[0].size, 1);
+    assert_eq!(
+        Uart::REGISTERS[1],
+        RegisterInfo {
+            name: "fifo",
+            offset: 4,
+            size: 4,
+            len: None,
+            access: RegisterAccess::READ.union(RegisterAccess::WRITE),
+            inner: None,
+        }
+    );
+    assert_eq!(
+        Uart::REGISTERS[2],
+        RegisterInfo {
+            name: "array",
+            offset: 8,
+            size: 2,
+            len: Some(4),
+            access: RegisterAccess::PURE_READ.union(RegisterAccess::WRITE),
+            inner: None,
+        }
+    );
+    assert_eq!(
+        Uart::REGISTERS[3],
+        RegisterInfo {
+            name: "banks",
+            offset: 16,
+            size: 8,
+            len: Some(2),
+            access: RegisterAccess::NONE,
+            inner: Some(InnerBlockInfo {
+                name: "UartBank",
+                registers: UartBank::REGISTERS,
+            }),
+        }
+    );
+    assert_eq!(Uart::REGISTERS[3].total_size(), 16);
+    assert_eq!(<Uart as RegisterBlock>::REGISTERS, Uart::REGISTERS);
+
+    assert!(Uart::REGISTERS[1].access.is_readable());
+    assert!(!UartBank::REGISTERS[1].access.is_writable());
+    assert_eq!(
+        format!("{:?}", Uart::REGISTERS[0].access),
+        "PureRead | Write | Modify"
+    );
+    assert_eq!(format!("{:?}", RegisterAccess::NONE), "NONE");
+}
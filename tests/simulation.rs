@@ -0,0 +1,95 @@
+#![cfg(feature = "testing")]
+
+use std::sync::{Arc, Mutex};
+
+use derive_mmio::testing::{Access, RecordingPeripheral};
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    #[mmio(Read, Write)]
+    data: u32,
+    #[mmio(PureRead)]
+    status: u32,
+    #[mmio(W1C)]
+    interrupts: u32,
+}
+
+const READY: u32 = 1 << 0;
+
+fn main() {
+    let peripheral = RecordingPeripheral::new(Uart {
+        data: 0,
+        status: 0,
+        interrupts: 0b1011,
+    });
+
+    // The status register signals ready after the third read.
+    let mut reads = 0;
+    peripheral.on_read("status", move |stored| {
+        reads += 1;
+        if reads >= 3 {
+            *stored |= READY as u64;
+        }
+        *stored
+    });
+
+    // Reading the data register pops from an RX queue.
+    let rx = Arc::new(Mutex::new(vec![0x41, 0x42]));
+    let rx_hook = rx.clone();
+    peripheral.on_read("data", move |_| rx_hook.lock().unwrap().remove(0));
+
+    // Writing the data register pushes into a TX queue.
+    let tx = Arc::new(Mutex::new(Vec::new()));
+    let tx_hook = tx.clone();
+    peripheral.on_write("data", move |_, value| tx_hook.lock().unwrap().push(value));
+
+    peripheral.on_write("interrupts", |stored, value| *stored &= !value);
+
+    let mut uart = peripheral.mmio();
+    let mut polls = 1;
+    while uart.read_status() & READY == 0 {
+        polls += 1;
+    }
+    assert_eq!(polls, 3);
+
+    assert_eq!(uart.read_data(), 0x41);
+    assert_eq!(uart.read_data(), 0x42);
+    assert!(rx.lock().unwrap().is_empty());
+
+    uart.write_data(0x10);
+    uart.write_data(0x20);
+    assert_eq!(*tx.lock().unwrap(), [0x10, 0x20]);
+    // The hook did not store the written value.
+    assert_eq!(peripheral.accesses().len(), 7);
+    peripheral.clear();
+
+    uart.clear_interrupts(0b0010);
+    assert_eq!(uart.read_interrupts(), 0b1001);
+    assert_eq!(
+        peripheral.accesses(),
+        [
+            Access::write("interrupts", 0x8, 32, 0b0010),
+            Access::read("interrupts", 0x8, 32, 0b1001),
+        ]
+    );
+
+    // Hooks can access registers themselves. The peripheral is leaked, so that
+    // the hook can own a handle.
+    let peripheral = Box::leak(Box::new(RecordingPeripheral::new(Uart {
+        data: 0x55,
+        status: 0,
+        interrupts: 0,
+    })));
+    let mut hook_uart = peripheral.mmio();
+    peripheral.on_read("status", move |_| u64::from(hook_uart.read_data() != 0));
+    assert_eq!(peripheral.mmio().read_status(), 1);
+    // The access of the hook is logged first.
+    assert_eq!(
+        peripheral.accesses(),
+        [
+            Access::read("data", 0x0, 32, 0x55),
+            Access::read("status", 0x4, 32, 1),
+        ]
+    );
+}
@@ -0,0 +1,31 @@
+#[derive(derive_mmio::Mmio)]
+#[mmio(
+    no_ctors,
+    instances(TIMER0 = 0x4000_0000, TIMER1 = 0x4000_1000),
+    singletons
+)]
+#[repr(C)]
+struct Timer {
+    count: u32,
+}
+
+fn main() {
+    let timer0 = TIMER0::take().unwrap();
+    assert!(TIMER0::take().is_none());
+
+    // The other instance is tracked separately.
+    let timer1 = TIMER1::take().unwrap();
+    assert!(TIMER1::take().is_none());
+    assert_eq!(unsafe { timer1.ptr() }.addr(), 0x4000_1000);
+
+    // The taken handle is a regular handle with a static lifetime.
+    let _: &MmioTimer<'static> = &timer1;
+
+    timer0.release();
+    let timer0 = MmioTimerAt::<0x4000_0000>::take().unwrap();
+    assert!(TIMER0::take().is_none());
+    assert_eq!(unsafe { timer0.ptr() }.addr(), 0x4000_0000);
+
+    timer1.release();
+    assert!(TIMER1::take().is_some());
+}
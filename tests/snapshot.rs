@@ -0,0 +1,71 @@
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct UartBank {
+    data: u16,
+    #[mmio(Read)]
+    fifo: u16,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    data: u32,
+    #[mmio(PureRead)]
+    status: u8,
+    _reserved: [u8; 3],
+    #[mmio(Read, Write)]
+    fifo: u32,
+    #[mmio(PureRead, Write)]
+    array: [u32; 2],
+    #[mmio(Inner)]
+    bank: UartBank,
+    #[mmio(Inner)]
+    banks: [UartBank; 2],
+}
+
+fn main() {
+    let mut uart = Uart {
+        data: 0xA,
+        status: 0x5,
+        _reserved: [0xFF; 3],
+        fifo: 0xF,
+        array: [0x1, 0x2],
+        bank: UartBank {
+            data: 0x10,
+            fifo: 0x11,
+        },
+        banks: [
+            UartBank {
+                data: 0x20,
+                fifo: 0x21,
+            },
+            UartBank {
+                data: 0x30,
+                fifo: 0x31,
+            },
+        ],
+    };
+
+    // Safety: We're pointing at a real object
+    let mmio_uart = unsafe { Uart::new_mmio(core::ptr::addr_of_mut!(uart)) };
+    let snapshot = mmio_uart.snapshot();
+    assert_eq!(snapshot.value("data"), Some(0xA));
+    assert_eq!(snapshot.value("status"), Some(0x5));
+    assert_eq!(snapshot.value("array[1]"), Some(0x2));
+    assert_eq!(snapshot.value("bank.data"), Some(0x10));
+    assert_eq!(snapshot.value("banks[1].data"), Some(0x30));
+    // Registers with read side effects are not part of the snapshot
+    assert_eq!(snapshot.value("fifo"), None);
+    assert_eq!(snapshot.value("banks[0].fifo"), None);
+    // Neither are registers which don't exist
+    assert_eq!(snapshot.value("_reserved"), None);
+    assert_eq!(snapshot.value("array[2]"), None);
+    assert_eq!(snapshot.value("array"), None);
+
+    assert_eq!(
+        format!("{:?}", snapshot),
+        "Uart { data: 0x0000000a, status: 0x05, array: [0x00000001, 0x00000002], \
+         bank: UartBank { data: 0x0010 }, \
+         banks: [UartBank { data: 0x0020 }, UartBank { data: 0x0030 }] }"
+    );
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "testing")]
+
+use derive_mmio::testing::{Access, RecordingPeripheral};
+use derive_mmio::RegisterAccess;
+
+#[derive(derive_mmio::Mmio)]
+#[mmio(bus_width = 32)]
+#[repr(C)]
+struct Timer {
+    control: u32,
+    _reserved: u32,
+    #[mmio(PureRead, split64)]
+    counter: u64,
+    #[mmio(split64(order = "hi_lo", latch))]
+    compare: u64,
+    #[mmio(Read, split64(latch))]
+    capture: u64,
+}
+
+fn main() {
+    let peripheral = RecordingPeripheral::new(Timer {
+        control: 0,
+        _reserved: 0,
+        counter: 0x2_0000_0003,
+        compare: 0,
+        capture: 0x1234_5678_9ABC_DEF0,
+    });
+    let mut timer = peripheral.mmio();
+
+    // The low half rolls over between the first two reads of the high half, so
+    // the counter is read a second time.
+    let mut halves = [0x0, 0xFFFF_FFFF, 0x1, 0x1, 0x5, 0x1].into_iter();
+    peripheral.on_read("counter", move |stored| halves.next().unwrap_or(*stored));
+    assert_eq!(timer.read_counter(), 0x1_0000_0005);
+    assert_eq!(
+        peripheral.take_accesses(),
+        [
+            Access::read("counter", 0xC, 32, 0x0),
+            Access::read("counter", 0x8, 32, 0xFFFF_FFFF),
+            Access::read("counter", 0xC, 32, 0x1),
+            Access::read("counter", 0xC, 32, 0x1),
+            Access::read("counter", 0x8, 32, 0x5),
+            Access::read("counter", 0xC, 32, 0x1),
+        ]
+    );
+
+    timer.write_compare(0x1122_3344_5566_7788);
+    timer.modify_compare(|compare| compare + 1);
+    assert_eq!(
+        peripheral.take_accesses(),
+        [
+            Access::write("compare", 0x14, 32, 0x1122_3344),
+            Access::write("compare", 0x10, 32, 0x5566_7788),
+            Access::read("compare", 0x14, 32, 0x1122_3344),
+            Access::read("compare", 0x10, 32, 0x5566_7788),
+            Access::write("compare", 0x14, 32, 0x1122_3344),
+            Access::write("compare", 0x10, 32, 0x5566_7789),
+        ]
+    );
+
+    assert_eq!(timer.read_capture(), 0x1234_5678_9ABC_DEF0);
+    assert_eq!(
+        peripheral.take_accesses(),
+        [
+            Access::read("capture", 0x18, 32, 0x9ABC_DEF0),
+            Access::read("capture", 0x1C, 32, 0x1234_5678),
+        ]
+    );
+
+    // Snapshots read split registers in halves as well, in the same order as the
+    // read functions.
+    let snapshot = timer.snapshot();
+    assert_eq!(snapshot.value("counter"), Some(0x2_0000_0003));
+    assert_eq!(snapshot.value("compare"), Some(0x1122_3344_5566_7789));
+    assert_eq!(snapshot.value("capture"), None);
+    assert_eq!(
+        peripheral.take_accesses(),
+        [
+            Access::read("control", 0x0, 32, 0x0),
+            Access::read("counter", 0xC, 32, 0x2),
+            Access::read("counter", 0x8, 32, 0x3),
+            Access::read("counter", 0xC, 32, 0x2),
+            Access::read("compare", 0x14, 32, 0x1122_3344),
+            Access::read("compare", 0x10, 32, 0x5566_7789),
+        ]
+    );
+
+    let counter = Timer::REGISTERS
+        .iter()
+        .find(|register| register.name == "counter")
+        .unwrap();
+    assert_eq!(counter.size, 8);
+    assert_eq!(
+        counter.access,
+        RegisterAccess::PURE_READ.union(RegisterAccess::SPLIT64)
+    );
+    assert_eq!(format!("{:?}", counter.access), "PureRead | Split64");
+    let compare = Timer::REGISTERS
+        .iter()
+        .find(|register| register.name == "compare")
+        .unwrap();
+    assert_eq!(
+        format!("{:?}", compare.access),
+        "PureRead | Write | Modify | Split64 | Split64HiFirst | Split64Latch"
+    );
+}
@@ -0,0 +1,93 @@
+use derive_mmio::svd::Peripheral;
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Channel {
+    config: u16,
+    #[mmio(Write)]
+    trigger: u16,
+}
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Timer {
+    #[mmio(PureRead)]
+    count: u32,
+    #[mmio(PureRead, W1C)]
+    status: u32,
+    #[mmio(ClearOnRead)]
+    errors: u8,
+    _reserved: [u8; 3],
+    compare: [u32; 2],
+    #[mmio(Inner)]
+    channels: [Channel; 2],
+}
+
+fn main() {
+    let svd = Peripheral::<Timer>::new("TIMER0", 0x4000_0000)
+        .description("Timer <0>")
+        .to_string();
+    assert_eq!(
+        svd,
+        r#"<peripheral>
+  <name>TIMER0</name>
+  <description>Timer &lt;0&gt;</description>
+  <baseAddress>0x40000000</baseAddress>
+  <addressBlock>
+    <offset>0x0</offset>
+    <size>0x1c</size>
+    <usage>registers</usage>
+  </addressBlock>
+  <registers>
+    <register>
+      <name>count</name>
+      <addressOffset>0x0</addressOffset>
+      <size>32</size>
+      <access>read-only</access>
+    </register>
+    <register>
+      <name>status</name>
+      <addressOffset>0x4</addressOffset>
+      <size>32</size>
+      <access>read-write</access>
+      <modifiedWriteValues>oneToClear</modifiedWriteValues>
+    </register>
+    <register>
+      <name>errors</name>
+      <addressOffset>0x8</addressOffset>
+      <size>8</size>
+      <access>read-only</access>
+      <readAction>clear</readAction>
+    </register>
+    <register>
+      <dim>2</dim>
+      <dimIncrement>0x4</dimIncrement>
+      <name>compare[%s]</name>
+      <addressOffset>0xc</addressOffset>
+      <size>32</size>
+      <access>read-write</access>
+    </register>
+    <cluster>
+      <dim>2</dim>
+      <dimIncrement>0x4</dimIncrement>
+      <name>channels[%s]</name>
+      <description>Channel</description>
+      <addressOffset>0x14</addressOffset>
+      <register>
+        <name>config</name>
+        <addressOffset>0x0</addressOffset>
+        <size>16</size>
+        <access>read-write</access>
+      </register>
+      <register>
+        <name>trigger</name>
+        <addressOffset>0x2</addressOffset>
+        <size>16</size>
+        <access>write-only</access>
+      </register>
+    </cluster>
+  </registers>
+</peripheral>
+"#
+    );
+}
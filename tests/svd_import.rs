@@ -0,0 +1,94 @@
+#![cfg(feature = "svd-import")]
+
+use derive_mmio::svd::generate;
+
+/// The generated code, to check that it compiles.
+mod timer {
+    include!("svd_import/timer.rs");
+}
+
+const SVD: &str = include_str!("svd_import/timer.svd");
+
+fn main() {
+    let expected = include_str!("svd_import/timer.rs");
+    assert_eq!(generate(SVD, "TIMER0").unwrap(), expected);
+    assert_eq!(
+        generate(SVD, "TIMER1").unwrap(),
+        expected.replace("Timer0", "Timer1")
+    );
+
+    assert_eq!(core::mem::size_of::<timer::Timer0>(), 0x60);
+    assert_eq!(core::mem::size_of::<timer::TimerChannel>(), 0x10);
+    let registers = timer::Timer0::REGISTERS;
+    assert_eq!(registers[5].name, "compare");
+    assert_eq!(registers[5].len, Some(2));
+    assert_eq!(registers[7].name, "capture_b");
+    assert_eq!(registers[7].offset, 0x30);
+
+    assert_eq!(
+        generate(SVD, "TIMER2").unwrap_err().to_string(),
+        "there is no peripheral named `TIMER2`"
+    );
+    let misaligned = SVD.replace(
+        "<addressOffset>0xC</addressOffset>",
+        "<addressOffset>0xD</addressOffset>",
+    );
+    assert_eq!(
+        generate(&misaligned, "TIMER0").unwrap_err().to_string(),
+        "the register `errors` is not aligned"
+    );
+    let overlapping = SVD.replace(
+        "<addressOffset>0x8</addressOffset>\n          <modifiedWriteValues>",
+        "<addressOffset>0x6</addressOffset>\n          <modifiedWriteValues>",
+    );
+    assert_eq!(
+        generate(&overlapping, "TIMER0").unwrap_err().to_string(),
+        "the register `status` overlaps the previous register"
+    );
+
+    // Names which are the same after the conversion to field names
+    let colliding = SVD.replace("<name>COUNT</name>", "<name>Ctrl</name>");
+    assert_eq!(
+        generate(&colliding, "TIMER0").unwrap_err().to_string(),
+        "more than one register of `Timer0` is named `ctrl`"
+    );
+
+    // Clusters with the same structure name share the structure, if they match
+    let cluster = |registers: &str| {
+        SVD.replace(
+            "      </registers>\n    </peripheral>\n    <peripheral derivedFrom",
+            &format!(
+                "        <cluster>
+          <name>EXTRA</name>
+          <headerStructName>TIMER_CHANNEL</headerStructName>
+          <addressOffset>0x60</addressOffset>
+          {registers}
+        </cluster>
+      </registers>
+    </peripheral>
+    <peripheral derivedFrom"
+            ),
+        )
+    };
+    let shared = cluster(
+        "<register><name>CONFIG</name><addressOffset>0x0</addressOffset></register>
+          <register><name>TYPE</name><addressOffset>0x8</addressOffset><size>64</size></register>",
+    );
+    let code = generate(&shared, "TIMER0").unwrap();
+    assert_eq!(code.matches("pub struct TimerChannel {").count(), 1);
+    assert!(code.contains("    extra: TimerChannel,"));
+    let different =
+        cluster("<register><name>CONFIG</name><addressOffset>0x0</addressOffset></register>");
+    assert_eq!(
+        generate(&different, "TIMER0").unwrap_err().to_string(),
+        "the clusters named `TimerChannel` have different registers"
+    );
+    let same_name = SVD.replace(
+        "<headerStructName>TIMER_CHANNEL</headerStructName>",
+        "<headerStructName>TIMER0</headerStructName>",
+    );
+    assert_eq!(
+        generate(&same_name, "TIMER0").unwrap_err().to_string(),
+        "the peripheral and a cluster are both named `Timer0`"
+    );
+}
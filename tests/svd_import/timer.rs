@@ -0,0 +1,40 @@
+/// General purpose timer
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+pub struct Timer0 {
+    /// Control register
+    #[mmio(offset = 0x0)]
+    ctrl: u32,
+    #[mmio(PureRead, offset = 0x4)]
+    count: u32,
+    #[mmio(PureRead, W1C, offset = 0x8)]
+    status: u32,
+    #[mmio(ClearOnRead, offset = 0xc)]
+    errors: u16,
+    _reserved0: [u8; 0x2],
+    #[mmio(Write, offset = 0x10)]
+    trigger: u8,
+    _reserved1: [u8; 0xf],
+    #[mmio(offset = 0x20)]
+    compare: [u32; 2],
+    #[mmio(Read, offset = 0x28)]
+    capture_a: u32,
+    _reserved2: [u8; 0x4],
+    #[mmio(Read, offset = 0x30)]
+    capture_b: u32,
+    _reserved3: [u8; 0xc],
+    /// Channel
+    #[mmio(Inner, offset = 0x40)]
+    ch: [TimerChannel; 2],
+}
+
+/// Channel
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+pub struct TimerChannel {
+    #[mmio(offset = 0x0)]
+    config: u32,
+    _reserved0: [u8; 0x4],
+    #[mmio(offset = 0x8)]
+    r#type: u64,
+}
@@ -10,6 +10,21 @@ fn all_tests() {
     t.pass("tests/inner_mmio_array.rs");
     t.pass("tests/no_ctors.rs");
     t.pass("tests/derives_debug.rs");
+    t.pass("tests/custom_backend.rs");
+    t.pass("tests/offsets.rs");
+    t.pass("tests/mmio_block.rs");
+    t.pass("tests/registers.rs");
+    t.pass("tests/snapshot.rs");
+    t.pass("tests/write_one.rs");
+    t.pass("tests/register_widths.rs");
+    t.pass("tests/odd_size_register.rs");
+    t.pass("tests/endian.rs");
+    t.pass("tests/generics.rs");
+    t.pass("tests/instances.rs");
+    t.pass("tests/singletons.rs");
+    t.pass("tests/address_ranges.rs");
+    t.pass("tests/svd.rs");
+    t.pass("tests/c_header.rs");
 
     // tests that pass but need an specific rustc version
 
@@ -17,11 +32,34 @@ fn all_tests() {
         t.pass("tests/constness.rs");
     }
 
+    // tests that pass but need an optional feature
+
+    if cfg!(feature = "testing") {
+        t.pass("tests/recording.rs");
+        t.pass("tests/simulation.rs");
+        t.pass("tests/fifo.rs");
+        t.pass("tests/split64.rs");
+    }
+
+    if cfg!(feature = "critical-section") {
+        t.pass("tests/critical_section.rs");
+    }
+
+    if cfg!(feature = "svd-import") {
+        t.pass("tests/svd_import.rs");
+    }
+
+    if cfg!(all(feature = "std", target_os = "linux")) {
+        t.pass("tests/linux.rs");
+    }
+
     // tests that fail
 
     t.compile_fail("tests/no_compile/array_safe_unchecked.rs");
     t.compile_fail("tests/no_compile/bad_inner_attr.rs");
+    t.compile_fail("tests/no_compile/address_range_inclusive.rs");
     t.compile_fail("tests/no_compile/bad_outer_attr.rs");
+    t.compile_fail("tests/no_compile/backend_unsupported_size.rs");
     t.compile_fail("tests/no_compile/cant_fake_inner_block.rs");
     t.compile_fail("tests/no_compile/double_read.rs");
     t.compile_fail("tests/no_compile/duplicate_field_attr.rs");
@@ -32,9 +70,23 @@ fn all_tests() {
     t.compile_fail("tests/no_compile/modify_standalone.rs");
     t.compile_fail("tests/no_compile/modify_without_read.rs");
     t.compile_fail("tests/no_compile/modify_without_write.rs");
+    t.compile_fail("tests/no_compile/modify_w1c.rs");
+    t.compile_fail("tests/no_compile/modify_fifo.rs");
+    t.compile_fail("tests/no_compile/bus_width_too_narrow.rs");
+    t.compile_fail("tests/no_compile/bus_width_invalid.rs");
+    t.compile_fail("tests/no_compile/split64_not_u64.rs");
+    t.compile_fail("tests/no_compile/endian_invalid.rs");
+    t.compile_fail("tests/no_compile/generic_no_ctors.rs");
+    t.compile_fail("tests/no_compile/generic_padding.rs");
+    t.compile_fail("tests/no_compile/generic_reserved_name.rs");
     t.compile_fail("tests/no_compile/no_modify.rs");
+    t.compile_fail("tests/no_compile/offset_without_value.rs");
+    t.compile_fail("tests/no_compile/overlapping_offset.rs");
     t.compile_fail("tests/no_compile/padding_forbidden.rs");
     t.compile_fail("tests/no_compile/read_only.rs");
     t.compile_fail("tests/no_compile/repr_c_mandatory.rs");
+    t.compile_fail("tests/no_compile/singletons_without_instances.rs");
+    t.compile_fail("tests/no_compile/snapshot_unsupported_size.rs");
     t.compile_fail("tests/no_compile/unimpl_send.rs");
+    t.compile_fail("tests/no_compile/wrong_offset.rs");
 }
@@ -0,0 +1,43 @@
+use derive_mmio::RegisterAccess;
+
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+struct Uart {
+    #[mmio(W1C)]
+    interrupts: u32,
+    #[mmio(Read, W1C, W1S)]
+    events: u32,
+    #[mmio(W1S)]
+    channels: [u32; 2],
+}
+
+fn main() {
+    let mut uart = Uart {
+        interrupts: 0b1011,
+        events: 0,
+        channels: [0; 2],
+    };
+
+    // Safety: We're pointing at a real object
+    let mut mmio_uart = unsafe { Uart::new_mmio(core::ptr::addr_of_mut!(uart)) };
+    // Plain memory does not clear or set any bits, so the mask is simply stored.
+    assert_eq!(mmio_uart.read_interrupts(), 0b1011);
+    mmio_uart.clear_interrupts(0b0010);
+    assert_eq!(mmio_uart.read_interrupts(), 0b0010);
+    mmio_uart.set_events(0b100);
+    assert_eq!(mmio_uart.read_events(), 0b100);
+    mmio_uart.clear_events(0b1);
+    assert_eq!(mmio_uart.read_events(), 0b1);
+    mmio_uart.set_channels(1, 0b1).unwrap();
+    assert_eq!(mmio_uart.read_channels(1).unwrap(), 0b1);
+    assert!(mmio_uart.set_channels(2, 0b1).is_err());
+
+    assert_eq!(
+        Uart::REGISTERS[0].access,
+        RegisterAccess::PURE_READ.union(RegisterAccess::W1C)
+    );
+    assert_eq!(
+        format!("{:?}", Uart::REGISTERS[1].access),
+        "Read | W1C | W1S"
+    );
+}